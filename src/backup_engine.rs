@@ -1,10 +1,13 @@
 extern crate libc;
 
+use env::Env;
+use rocksdb::DB;
 use rocksdb_ffi;
-use rocksdb_ffi::{error_message};
+use rocksdb_ffi::{error_message, path_to_cstring};
+use rocksdb_options::Options;
 
 use self::libc::{c_int, c_char, c_void};
-use std::ffi::{CString};
+use std::path::Path;
 
 #[derive(Copy, Clone)]
 #[repr(C)]
@@ -18,6 +21,10 @@ pub struct rocksdb_restore_options_t(*const c_void);
 #[repr(C)]
 struct rocksdb_backup_engine_info_t(*const c_void);
 
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct rocksdb_backup_engine_options_t(*const c_void);
+
 unsafe impl Send for rocksdb_backup_engine_t {}
 unsafe impl Sync for rocksdb_backup_engine_t {}
 
@@ -27,14 +34,56 @@ extern {
     fn rocksdb_restore_options_destroy(opt: rocksdb_restore_options_t);
     fn rocksdb_restore_options_set_keep_log_files(opt: rocksdb_restore_options_t, v: c_int);
 
+    fn rocksdb_backup_engine_options_create(
+        backup_dir: *const c_char) -> rocksdb_backup_engine_options_t;
+    fn rocksdb_backup_engine_options_destroy(
+        opt: rocksdb_backup_engine_options_t);
+    fn rocksdb_backup_engine_options_set_backup_dir(
+        opt: rocksdb_backup_engine_options_t, backup_dir: *const c_char);
+    fn rocksdb_backup_engine_options_set_env(
+        opt: rocksdb_backup_engine_options_t, env: rocksdb_ffi::DBEnv);
+    fn rocksdb_backup_engine_options_set_share_table_files(
+        opt: rocksdb_backup_engine_options_t, v: u8);
+    fn rocksdb_backup_engine_options_set_sync(
+        opt: rocksdb_backup_engine_options_t, v: u8);
+    fn rocksdb_backup_engine_options_set_destroy_old_data(
+        opt: rocksdb_backup_engine_options_t, v: u8);
+    fn rocksdb_backup_engine_options_set_backup_log_files(
+        opt: rocksdb_backup_engine_options_t, v: u8);
+    fn rocksdb_backup_engine_options_set_backup_rate_limit(
+        opt: rocksdb_backup_engine_options_t, limit: u64);
+    fn rocksdb_backup_engine_options_set_restore_rate_limit(
+        opt: rocksdb_backup_engine_options_t, limit: u64);
+    fn rocksdb_backup_engine_options_set_max_background_operations(
+        opt: rocksdb_backup_engine_options_t, v: c_int);
+    fn rocksdb_backup_engine_options_set_share_files_with_checksum_naming(
+        opt: rocksdb_backup_engine_options_t, v: c_int);
+
     fn rocksdb_backup_engine_open(
         options: rocksdb_ffi::DBOptions,
         path: *const c_char,
         err: *mut *const i8) -> rocksdb_backup_engine_t;
+    fn rocksdb_backup_engine_open_opts(
+        options: rocksdb_backup_engine_options_t,
+        env: rocksdb_ffi::DBEnv,
+        err: *mut *const i8) -> rocksdb_backup_engine_t;
     fn rocksdb_backup_engine_create_new_backup(
         be: rocksdb_backup_engine_t,
         db: rocksdb_ffi::DBInstance,
         err: *mut *const i8);
+    fn rocksdb_backup_engine_create_new_backup_flush(
+        be: rocksdb_backup_engine_t,
+        db: rocksdb_ffi::DBInstance,
+        flush_before_backup: u8,
+        err: *mut *const i8);
+    fn rocksdb_backup_engine_purge_old_backups(
+        be: rocksdb_backup_engine_t,
+        num_backups_to_keep: u32,
+        err: *mut *const i8);
+    fn rocksdb_backup_engine_verify_backup(
+        be: rocksdb_backup_engine_t,
+        backup_id: u32,
+        err: *mut *const i8);
 
     fn rocksdb_backup_engine_close(be: rocksdb_backup_engine_t);
     fn rocksdb_backup_engine_restore_db_from_latest_backup(
@@ -43,6 +92,37 @@ extern {
         wal_dir: *const c_char,
         restore_options: rocksdb_restore_options_t,
         err: *mut *const i8);
+    fn rocksdb_backup_engine_restore_db_from_backup(
+        be: rocksdb_backup_engine_t,
+        db_dir: *const c_char,
+        wal_dir: *const c_char,
+        restore_options: rocksdb_restore_options_t,
+        backup_id: u32,
+        err: *mut *const i8);
+
+    fn rocksdb_backup_engine_get_backup_info(
+        be: rocksdb_backup_engine_t) -> rocksdb_backup_engine_info_t;
+    fn rocksdb_backup_engine_info_count(
+        info: rocksdb_backup_engine_info_t) -> c_int;
+    fn rocksdb_backup_engine_info_timestamp(
+        info: rocksdb_backup_engine_info_t, index: c_int) -> i64;
+    fn rocksdb_backup_engine_info_backup_id(
+        info: rocksdb_backup_engine_info_t, index: c_int) -> u32;
+    fn rocksdb_backup_engine_info_size(
+        info: rocksdb_backup_engine_info_t, index: c_int) -> u64;
+    fn rocksdb_backup_engine_info_number_files(
+        info: rocksdb_backup_engine_info_t, index: c_int) -> u32;
+    fn rocksdb_backup_engine_info_destroy(info: rocksdb_backup_engine_info_t);
+}
+
+/// Describes one backup held by a `BackupEngine`.
+pub struct BackupEngineInfo {
+    pub backup_id: u32,
+    /// Seconds since the epoch at which the backup was taken.
+    pub timestamp: i64,
+    /// Size of the backup in bytes.
+    pub size: u64,
+    pub num_files: u32,
 }
 
 pub struct RestoreOption {
@@ -54,8 +134,11 @@ impl RestoreOption {
         let inner = unsafe { rocksdb_restore_options_create() };
         RestoreOption { inner: inner }
     }
-    pub fn set_keep_log_files(&mut self, v: c_int) {
-        unsafe { rocksdb_restore_options_set_keep_log_files(self.inner, v) }
+    /// When set, log files already in the WAL directory are left in place
+    /// instead of being replaced by the backup's. Meant for backups taken
+    /// with `BackupEngineOptions::set_backup_log_files(false)`.
+    pub fn set_keep_log_files(&mut self, v: bool) {
+        unsafe { rocksdb_restore_options_set_keep_log_files(self.inner, v as c_int) }
     }
 }
 
@@ -65,35 +148,159 @@ impl Drop for RestoreOption {
     }
 }
 
+/// How shared table files are named in the backup directory, see
+/// `BackupEngineOptions::set_share_files_with_checksum_naming`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ShareFilesNaming {
+    /// `<file_number>_<crc32c>_<file_size>.sst`, which requires reading
+    /// every file to name it. Only useful to keep the names old backups use.
+    LegacyCrc32cAndFileSize = 1,
+    /// `<file_number>_s<db_session_id>.sst`, the default.
+    UseDbSessionId = 2,
+}
+
+// RocksDB's kFlagIncludeFileSize.
+const SHARE_FILES_INCLUDE_FILE_SIZE: u32 = 1 << 31;
+
+pub struct BackupEngineOptions {
+    inner: rocksdb_backup_engine_options_t,
+    backup_env: Option<Env>,
+}
+
+impl BackupEngineOptions {
+    pub fn new<P: AsRef<Path>>(backup_dir: P) -> Result<BackupEngineOptions, String> {
+        let cpath = path_to_cstring(backup_dir.as_ref())?;
+        let inner = unsafe { rocksdb_backup_engine_options_create(cpath.as_ptr()) };
+        Ok(BackupEngineOptions { inner: inner, backup_env: None })
+    }
+
+    pub fn set_backup_dir<P: AsRef<Path>>(&mut self, backup_dir: P) -> Result<(), String> {
+        let cpath = path_to_cstring(backup_dir.as_ref())?;
+        unsafe { rocksdb_backup_engine_options_set_backup_dir(self.inner, cpath.as_ptr()) }
+        Ok(())
+    }
+
+    /// Keeps the backup directory in `env` rather than in the DB's own
+    /// environment.
+    pub fn set_backup_env(&mut self, env: &Env) {
+        unsafe { rocksdb_backup_engine_options_set_env(self.inner, env.inner()) }
+        self.backup_env = Some(env.clone());
+    }
+
+    /// Share SST files between backups instead of copying each one again.
+    /// RocksDB's share_files_with_checksum, which keeps shared files apart
+    /// by content rather than file number alone, has no C API setter and
+    /// stays on; `set_share_files_with_checksum_naming` picks its scheme.
+    pub fn set_share_table_files(&mut self, v: bool) {
+        unsafe { rocksdb_backup_engine_options_set_share_table_files(self.inner, v as u8) }
+    }
+
+    pub fn set_sync(&mut self, v: bool) {
+        unsafe { rocksdb_backup_engine_options_set_sync(self.inner, v as u8) }
+    }
+
+    /// Wipe any existing backups when the engine is opened.
+    pub fn set_destroy_old_data(&mut self, v: bool) {
+        unsafe { rocksdb_backup_engine_options_set_destroy_old_data(self.inner, v as u8) }
+    }
+
+    pub fn set_backup_log_files(&mut self, v: bool) {
+        unsafe { rocksdb_backup_engine_options_set_backup_log_files(self.inner, v as u8) }
+    }
+
+    /// Caps backup copying at `bytes_per_sec`; 0 means unlimited.
+    pub fn set_backup_rate_limit(&mut self, bytes_per_sec: u64) {
+        unsafe { rocksdb_backup_engine_options_set_backup_rate_limit(self.inner, bytes_per_sec) }
+    }
+
+    /// Caps restore copying at `bytes_per_sec`; 0 means unlimited.
+    pub fn set_restore_rate_limit(&mut self, bytes_per_sec: u64) {
+        unsafe { rocksdb_backup_engine_options_set_restore_rate_limit(self.inner, bytes_per_sec) }
+    }
+
+    pub fn set_max_background_operations(&mut self, n: c_int) {
+        unsafe { rocksdb_backup_engine_options_set_max_background_operations(self.inner, n) }
+    }
+
+    /// Names shared table files using `naming`, with `_<file_size>` added
+    /// before the extension when `include_file_size` is set and the scheme
+    /// doesn't have it already. Defaults to `UseDbSessionId` with the file
+    /// size.
+    pub fn set_share_files_with_checksum_naming(&mut self,
+                                                naming: ShareFilesNaming,
+                                                include_file_size: bool) {
+        let mut v = naming as u32;
+        if include_file_size {
+            v |= SHARE_FILES_INCLUDE_FILE_SIZE;
+        }
+        unsafe {
+            rocksdb_backup_engine_options_set_share_files_with_checksum_naming(
+                self.inner, v as c_int)
+        }
+    }
+}
+
+impl Drop for BackupEngineOptions {
+    fn drop(&mut self) {
+        unsafe { rocksdb_backup_engine_options_destroy(self.inner); }
+    }
+}
+
+/// Takes and restores backups of a `DB`. There is no `delete_backup`, as
+/// RocksDB's C API has no binding for deleting a single backup; use
+/// `purge_old_backups` instead. Likewise there is no
+/// `create_new_backup_with_metadata`, as the C API can't attach app
+/// metadata to a backup.
 pub struct BackupEngine {
-    inner: rocksdb_backup_engine_t
+    inner: rocksdb_backup_engine_t,
+    // RocksDB only borrows these, so they have to live as long as we do.
+    _env: Option<Env>,
+    _backup_env: Option<Env>,
 }
 
 impl BackupEngine {
-    pub fn new(db_options: rocksdb_ffi::DBOptions, path: &str) -> Result<BackupEngine, String> {
-        let cpath = match CString::new(path.as_bytes()) {
-            Ok(c) => c,
-            Err(_) => return Err("Failed to convert path to CString when opening backup".to_string()),
+    pub fn new<P: AsRef<Path>>(opts: &Options, path: P) -> Result<BackupEngine, String> {
+        let cpath = path_to_cstring(path.as_ref())?;
+
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        let back_up_engine: rocksdb_backup_engine_t = unsafe {
+            rocksdb_backup_engine_open(opts.inner, cpath.as_ptr(), err_ptr)
         };
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
 
+        Ok(BackupEngine { inner: back_up_engine, _env: None, _backup_env: None })
+    }
+
+    /// Opens a backup engine configured by `opts`, for DBs living in `env`.
+    pub fn open(opts: &BackupEngineOptions, env: &Env) -> Result<BackupEngine, String> {
         let mut err: *const i8 = 0 as *const i8;
         let err_ptr: *mut *const i8 = &mut err;
         let back_up_engine: rocksdb_backup_engine_t = unsafe {
-            rocksdb_backup_engine_open(db_options, cpath.as_ptr(), err_ptr)
+            rocksdb_backup_engine_open_opts(opts.inner, env.inner(), err_ptr)
         };
         if !err.is_null() {
             return Err(error_message(err));
         }
 
-        Ok(BackupEngine { inner: back_up_engine })
+        Ok(BackupEngine {
+            inner: back_up_engine,
+            _env: Some(env.clone()),
+            _backup_env: opts.backup_env.clone(),
+        })
     }
 
-    pub fn create_new_backup(&self, db: rocksdb_ffi::DBInstance) -> Result<(),String> {
+    /// Blocks until the backup is complete. RocksDB's C API has no progress
+    /// callback and no way to call StopBackup, so a running backup can't be
+    /// observed or cancelled from Rust.
+    pub fn create_new_backup(&self, db: &DB) -> Result<(),String> {
         let mut err: *const i8 = 0 as *const i8;
         let err_ptr: *mut *const i8 = &mut err;
 
         unsafe {
-            rocksdb_backup_engine_create_new_backup(self.inner, db, err_ptr)
+            rocksdb_backup_engine_create_new_backup(self.inner, db.inner, err_ptr)
         }
 
         if !err.is_null() {
@@ -103,25 +310,50 @@ impl BackupEngine {
         Ok(())
     }
 
-    pub fn restore_from_latest_backup(&self, db_dir: &str, wal_dir: &str, keep_wal: bool) -> Result<(), String> {
-        let c_db_dir = CString::new(db_dir.as_bytes()).unwrap();
-        let c_wal_dir = CString::new(wal_dir.as_bytes()).unwrap();
-
+    /// Like `create_new_backup`, but lets the caller skip flushing the
+    /// memtables first, in which case the WAL files are backed up instead.
+    pub fn create_new_backup_flush(&self, db: &DB, flush_before_backup: bool) -> Result<(),String> {
         let mut err: *const i8 = 0 as *const i8;
         let err_ptr: *mut *const i8 = &mut err;
 
+        unsafe {
+            rocksdb_backup_engine_create_new_backup_flush(self.inner,
+                                                          db.inner,
+                                                          flush_before_backup as u8,
+                                                          err_ptr)
+        }
+
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+
+        Ok(())
+    }
+
+    pub fn restore_from_latest_backup<D, W>(&self, db_dir: D, wal_dir: W, keep_wal: bool) -> Result<(), String>
+        where D: AsRef<Path>, W: AsRef<Path>
+    {
         let restore_options = &mut RestoreOption::new();
 
-        restore_options.set_keep_log_files(match keep_wal {
-            true => 1,
-            false => 0,
-        });
+        restore_options.set_keep_log_files(keep_wal);
+
+        self.restore_from_latest_backup_opt(db_dir, wal_dir, restore_options)
+    }
+
+    pub fn restore_from_latest_backup_opt<D, W>(&self, db_dir: D, wal_dir: W, opts: &RestoreOption) -> Result<(), String>
+        where D: AsRef<Path>, W: AsRef<Path>
+    {
+        let c_db_dir = path_to_cstring(db_dir.as_ref())?;
+        let c_wal_dir = path_to_cstring(wal_dir.as_ref())?;
+
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
 
         unsafe {
             rocksdb_backup_engine_restore_db_from_latest_backup(self.inner,
                                                                 c_db_dir.as_ptr(),
                                                                 c_wal_dir.as_ptr(),
-                                                                restore_options.inner,
+                                                                opts.inner,
                                                                 err_ptr)
         }
 
@@ -131,6 +363,95 @@ impl BackupEngine {
 
         Ok(())
     }
+
+    pub fn restore_from_backup<D, W>(&self, backup_id: u32, db_dir: D, wal_dir: W, keep_wal: bool) -> Result<(), String>
+        where D: AsRef<Path>, W: AsRef<Path>
+    {
+        let restore_options = &mut RestoreOption::new();
+
+        restore_options.set_keep_log_files(keep_wal);
+
+        self.restore_from_backup_opt(backup_id, db_dir, wal_dir, restore_options)
+    }
+
+    pub fn restore_from_backup_opt<D, W>(&self, backup_id: u32, db_dir: D, wal_dir: W, opts: &RestoreOption) -> Result<(), String>
+        where D: AsRef<Path>, W: AsRef<Path>
+    {
+        let c_db_dir = path_to_cstring(db_dir.as_ref())?;
+        let c_wal_dir = path_to_cstring(wal_dir.as_ref())?;
+
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+
+        unsafe {
+            rocksdb_backup_engine_restore_db_from_backup(self.inner,
+                                                         c_db_dir.as_ptr(),
+                                                         c_wal_dir.as_ptr(),
+                                                         opts.inner,
+                                                         backup_id,
+                                                         err_ptr)
+        }
+
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+
+        Ok(())
+    }
+
+    pub fn get_backup_info(&self) -> Result<Vec<BackupEngineInfo>, String> {
+        let info = unsafe { rocksdb_backup_engine_get_backup_info(self.inner) };
+        if info.0.is_null() {
+            return Err("Could not get backup info".to_string());
+        }
+
+        let backups = unsafe {
+            let count = rocksdb_backup_engine_info_count(info);
+            let backups = (0..count).map(|i| BackupEngineInfo {
+                backup_id: rocksdb_backup_engine_info_backup_id(info, i),
+                timestamp: rocksdb_backup_engine_info_timestamp(info, i),
+                size: rocksdb_backup_engine_info_size(info, i),
+                num_files: rocksdb_backup_engine_info_number_files(info, i),
+            }).collect();
+            rocksdb_backup_engine_info_destroy(info);
+            backups
+        };
+
+        Ok(backups)
+    }
+
+    /// Deletes all but the newest `num_backups_to_keep` backups.
+    pub fn purge_old_backups(&self, num_backups_to_keep: u32) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+
+        unsafe {
+            rocksdb_backup_engine_purge_old_backups(self.inner, num_backups_to_keep, err_ptr)
+        }
+
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the files of a backup are present and have the expected
+    /// sizes. File contents are not checksummed.
+    pub fn verify_backup(&self, backup_id: u32) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+
+        unsafe {
+            rocksdb_backup_engine_verify_backup(self.inner, backup_id, err_ptr)
+        }
+
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+
+        Ok(())
+    }
 }
 
 impl Drop for BackupEngine {
@@ -0,0 +1,103 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate libc;
+use self::libc::{c_int, size_t};
+use std::sync::Arc;
+
+use rocksdb_ffi;
+
+/// A block cache that can be shared between databases and column families
+/// through `BlockBasedOptions::set_block_cache`, so they all draw on one
+/// memory budget. Clones refer to the same underlying cache.
+///
+/// RocksDB's high-priority pool (`high_pri_pool_ratio`) can't be configured,
+/// as the C API has no setter for it, so every cache uses RocksDB's default.
+#[derive(Clone)]
+pub struct Cache {
+    inner: Arc<CacheHandle>,
+}
+
+struct CacheHandle(rocksdb_ffi::DBCache);
+
+unsafe impl Send for CacheHandle {}
+unsafe impl Sync for CacheHandle {}
+
+impl Drop for CacheHandle {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_cache_destroy(self.0);
+        }
+    }
+}
+
+impl Cache {
+    /// An LRU cache holding up to `capacity` bytes.
+    pub fn new_lru_cache(capacity: usize) -> Cache {
+        Cache::new_lru_cache_with_shard_bits(capacity, -1)
+    }
+
+    /// An LRU cache split into `2^num_shard_bits` independently locked
+    /// shards. A negative `num_shard_bits` lets RocksDB pick based on the
+    /// capacity.
+    pub fn new_lru_cache_with_shard_bits(capacity: usize,
+                                         num_shard_bits: i32)
+                                         -> Cache {
+        let cache = unsafe {
+            let opts = rocksdb_ffi::rocksdb_lru_cache_options_create();
+            rocksdb_ffi::rocksdb_lru_cache_options_set_capacity(
+                opts,
+                capacity as size_t);
+            rocksdb_ffi::rocksdb_lru_cache_options_set_num_shard_bits(
+                opts,
+                num_shard_bits as c_int);
+            let cache = rocksdb_ffi::rocksdb_cache_create_lru_opts(opts);
+            rocksdb_ffi::rocksdb_lru_cache_options_destroy(opts);
+            cache
+        };
+        if cache.0.is_null() {
+            panic!("Could not create rocksdb cache");
+        }
+        Cache { inner: Arc::new(CacheHandle(cache)) }
+    }
+
+    pub fn set_capacity(&self, capacity: usize) {
+        unsafe {
+            rocksdb_ffi::rocksdb_cache_set_capacity(self.inner.0,
+                                                    capacity as size_t);
+        }
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        unsafe { rocksdb_ffi::rocksdb_cache_get_capacity(self.inner.0) as usize }
+    }
+
+    /// The number of bytes currently held in the cache.
+    pub fn get_usage(&self) -> usize {
+        unsafe { rocksdb_ffi::rocksdb_cache_get_usage(self.inner.0) as usize }
+    }
+
+    /// The number of bytes held by entries that are in use and can't be
+    /// evicted.
+    pub fn get_pinned_usage(&self) -> usize {
+        unsafe {
+            rocksdb_ffi::rocksdb_cache_get_pinned_usage(self.inner.0) as usize
+        }
+    }
+
+    pub fn inner(&self) -> rocksdb_ffi::DBCache {
+        self.inner.0
+    }
+}
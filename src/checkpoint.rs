@@ -0,0 +1,76 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use std::marker::PhantomData;
+use std::path::Path;
+
+use rocksdb::DB;
+use rocksdb_ffi::{self, error_message, path_to_cstring};
+
+/// Takes openable snapshots of a live DB. SST files are hard-linked into the
+/// checkpoint when it lives on the same filesystem, and copied otherwise.
+pub struct Checkpoint<'a> {
+    inner: rocksdb_ffi::DBCheckpoint,
+    db: PhantomData<&'a DB>,
+}
+
+impl<'a> Checkpoint<'a> {
+    pub fn new(db: &'a DB) -> Result<Checkpoint<'a>, String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        let checkpoint = unsafe {
+            rocksdb_ffi::rocksdb_checkpoint_object_create(db.inner, err_ptr)
+        };
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        if checkpoint.0.is_null() {
+            return Err("Could not create checkpoint object.".to_string());
+        }
+        Ok(Checkpoint {
+            inner: checkpoint,
+            db: PhantomData,
+        })
+    }
+
+    /// Writes a checkpoint to `path`, which must not exist yet.
+    pub fn create_checkpoint<P: AsRef<Path>>(&self,
+                                             path: P)
+                                             -> Result<(), String> {
+        let cpath = path_to_cstring(path.as_ref())?;
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            // a log_size_for_flush of 0 always flushes the memtable first,
+            // so the checkpoint doesn't depend on replaying the WAL
+            rocksdb_ffi::rocksdb_checkpoint_create(self.inner,
+                                                   cpath.as_ptr(),
+                                                   0,
+                                                   err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Drop for Checkpoint<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_checkpoint_object_destroy(self.inner);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate libc;
+use self::libc::{c_char, c_int, c_void, size_t};
+use std::ffi::CString;
+use std::slice;
+
+use rocksdb_ffi::{self, DBCompactionFilter, DBCompactionFilterContext};
+
+/// What to do with a key/value pair encountered during compaction.
+pub enum Decision {
+    Keep,
+    Remove,
+    ChangeValue(Vec<u8>),
+}
+
+/// Decides, key by key, what survives a compaction. Any
+/// `FnMut(level, key, value) -> Decision` closure is a `CompactionFilter`.
+pub trait CompactionFilter {
+    fn filter(&mut self, level: u32, key: &[u8], value: &[u8]) -> Decision;
+}
+
+impl<F> CompactionFilter for F
+    where F: FnMut(u32, &[u8], &[u8]) -> Decision
+{
+    fn filter(&mut self, level: u32, key: &[u8], value: &[u8]) -> Decision {
+        self(level, key, value)
+    }
+}
+
+/// Describes the compaction a filter is being created for.
+pub struct CompactionFilterContext {
+    pub is_full_compaction: bool,
+    pub is_manual_compaction: bool,
+}
+
+impl CompactionFilterContext {
+    unsafe fn from_raw(ctx: DBCompactionFilterContext)
+                       -> CompactionFilterContext {
+        CompactionFilterContext {
+            is_full_compaction:
+                rocksdb_ffi::rocksdb_compactionfiltercontext_is_full_compaction(
+                    ctx),
+            is_manual_compaction:
+                rocksdb_ffi::rocksdb_compactionfiltercontext_is_manual_compaction(
+                    ctx),
+        }
+    }
+}
+
+/// Hands out a fresh `CompactionFilter` for every compaction RocksDB runs.
+/// `create` may be called concurrently from several background threads.
+pub trait CompactionFilterFactory {
+    type Filter: CompactionFilter;
+    fn create(&self, context: CompactionFilterContext) -> Self::Filter;
+}
+
+// Backs Options::set_compaction_filter: every compaction gets its own clone
+// of the filter, so a single filter never runs on two threads at once.
+pub(crate) struct CloneFactory<F>(pub(crate) F);
+
+impl<F> CompactionFilterFactory for CloneFactory<F>
+    where F: CompactionFilter + Clone
+{
+    type Filter = F;
+    fn create(&self, _: CompactionFilterContext) -> F {
+        self.0.clone()
+    }
+}
+
+struct CompactionFilterCallback<F> {
+    name: CString,
+    filter: F,
+    // RocksDB copies a changed value out of this buffer after the
+    // callback returns, so it has to outlive the call.
+    value: Vec<u8>,
+}
+
+pub(crate) struct CompactionFilterFactoryCallback<F> {
+    pub(crate) name: CString,
+    pub(crate) factory: F,
+}
+
+extern "C" fn destructor_callback<F>(raw_cb: *mut c_void) {
+    // turn this back into a local variable so rust will reclaim it
+    let _: Box<CompactionFilterCallback<F>> =
+        unsafe { Box::from_raw(raw_cb as *mut CompactionFilterCallback<F>) };
+}
+
+extern "C" fn name_callback<F>(raw_cb: *mut c_void) -> *const c_char {
+    unsafe {
+        let cb: &mut CompactionFilterCallback<F> =
+            &mut *(raw_cb as *mut CompactionFilterCallback<F>);
+        cb.name.as_ptr()
+    }
+}
+
+extern "C" fn filter_callback<F>(raw_cb: *mut c_void,
+                                 level: c_int,
+                                 raw_key: *const c_char,
+                                 key_len: size_t,
+                                 existing_value: *const c_char,
+                                 value_len: size_t,
+                                 new_value: *mut *mut c_char,
+                                 new_value_length: *mut size_t,
+                                 value_changed: *mut u8)
+                                 -> u8
+    where F: CompactionFilter
+{
+    unsafe {
+        let cb: &mut CompactionFilterCallback<F> =
+            &mut *(raw_cb as *mut CompactionFilterCallback<F>);
+        let key: &[u8] = slice::from_raw_parts(raw_key as *const u8,
+                                               key_len as usize);
+        let value: &[u8] = slice::from_raw_parts(existing_value as *const u8,
+                                                 value_len as usize);
+        match cb.filter.filter(level as u32, key, value) {
+            Decision::Keep => 0,
+            Decision::Remove => 1,
+            Decision::ChangeValue(v) => {
+                cb.value = v;
+                *new_value = cb.value.as_ptr() as *mut c_char;
+                *new_value_length = cb.value.len() as size_t;
+                *value_changed = 1;
+                0
+            }
+        }
+    }
+}
+
+pub(crate) extern "C" fn factory_destructor_callback<F>(raw_cb: *mut c_void) {
+    // turn this back into a local variable so rust will reclaim it
+    let _: Box<CompactionFilterFactoryCallback<F>> = unsafe {
+        Box::from_raw(raw_cb as *mut CompactionFilterFactoryCallback<F>)
+    };
+}
+
+pub(crate) extern "C" fn factory_name_callback<F>(raw_cb: *mut c_void)
+                                                  -> *const c_char {
+    unsafe {
+        let cb: &mut CompactionFilterFactoryCallback<F> =
+            &mut *(raw_cb as *mut CompactionFilterFactoryCallback<F>);
+        cb.name.as_ptr()
+    }
+}
+
+pub(crate) extern "C" fn create_filter_callback<F>(
+    raw_cb: *mut c_void,
+    ctx: DBCompactionFilterContext)
+    -> DBCompactionFilter
+    where F: CompactionFilterFactory
+{
+    unsafe {
+        let cb: &CompactionFilterFactoryCallback<F> =
+            &*(raw_cb as *const CompactionFilterFactoryCallback<F>);
+        let filter = cb.factory.create(CompactionFilterContext::from_raw(ctx));
+        let filter_cb = Box::new(CompactionFilterCallback {
+            name: cb.name.clone(),
+            filter: filter,
+            value: Vec::new(),
+        });
+        // RocksDB takes ownership of the filter and destroys it when the
+        // compaction finishes.
+        rocksdb_ffi::rocksdb_compactionfilter_create(
+            Box::into_raw(filter_cb) as *mut c_void,
+            destructor_callback::<F::Filter>,
+            filter_callback::<F::Filter>,
+            name_callback::<F::Filter>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocksdb::{ColumnFamilyDescriptor, DB, Writable};
+    use rocksdb_options::Options;
+    use super::*;
+
+    fn drop_tombstones(_: u32, _: &[u8], value: &[u8]) -> Decision {
+        if value == b"tombstone" {
+            Decision::Remove
+        } else {
+            Decision::Keep
+        }
+    }
+
+    struct TombstoneFactory;
+
+    impl CompactionFilterFactory for TombstoneFactory {
+        type Filter = fn(u32, &[u8], &[u8]) -> Decision;
+        fn create(&self, _: CompactionFilterContext) -> Self::Filter {
+            drop_tombstones
+        }
+    }
+
+    #[test]
+    fn compaction_filter_registers() {
+        let path = "_rust_rocksdb_compactionfiltertest";
+        {
+            let mut opts = Options::new();
+            opts.create_if_missing(true);
+            opts.set_compaction_filter("drop tombstones", drop_tombstones);
+            let mut db = DB::open(&opts, path).unwrap();
+            let mut cf_opts = Options::new();
+            cf_opts.set_compaction_filter_factory("tombstone factory", TombstoneFactory);
+            db.create_cf("cf1", &cf_opts).unwrap();
+            db.put(b"k1", b"v1").unwrap();
+            assert!(db.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "v1");
+        }
+        {
+            let mut opts = Options::new();
+            opts.set_compaction_filter("drop tombstones", drop_tombstones);
+            let mut cf_opts = Options::new();
+            cf_opts.set_compaction_filter_factory("tombstone factory", TombstoneFactory);
+            let cfs = vec![ColumnFamilyDescriptor::new("cf1", cf_opts)];
+            let db = DB::open_cf_descriptors(&opts, path, cfs).unwrap();
+            assert!(db.cf_handle("cf1").is_some());
+        }
+        assert!(DB::destroy(&Options::new(), path).is_ok());
+    }
+}
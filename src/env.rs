@@ -0,0 +1,75 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use std::sync::Arc;
+
+use rocksdb_ffi;
+
+/// RocksDB's interface to the filesystem and its background thread pools.
+/// Clones refer to the same underlying environment.
+#[derive(Clone)]
+pub struct Env {
+    inner: Arc<EnvHandle>,
+}
+
+struct EnvHandle(rocksdb_ffi::DBEnv);
+
+unsafe impl Send for EnvHandle {}
+unsafe impl Sync for EnvHandle {}
+
+impl Drop for EnvHandle {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_env_destroy(self.0);
+        }
+    }
+}
+
+impl Env {
+    /// The default, process-wide environment backed by the local
+    /// filesystem.
+    pub fn new() -> Env {
+        Env::from_raw(unsafe { rocksdb_ffi::rocksdb_create_default_env() })
+    }
+
+    /// An environment that keeps all files in memory.
+    pub fn mem_env() -> Env {
+        Env::from_raw(unsafe { rocksdb_ffi::rocksdb_create_mem_env() })
+    }
+
+    fn from_raw(env: rocksdb_ffi::DBEnv) -> Env {
+        if env.0.is_null() {
+            panic!("Could not create rocksdb env");
+        }
+        Env { inner: Arc::new(EnvHandle(env)) }
+    }
+
+    pub fn set_background_threads(&self, n: i32) {
+        unsafe {
+            rocksdb_ffi::rocksdb_env_set_background_threads(self.inner.0, n);
+        }
+    }
+
+    pub fn set_high_priority_background_threads(&self, n: i32) {
+        unsafe {
+            rocksdb_ffi::rocksdb_env_set_high_priority_background_threads(
+                self.inner.0, n);
+        }
+    }
+
+    pub fn inner(&self) -> rocksdb_ffi::DBEnv {
+        self.inner.0
+    }
+}
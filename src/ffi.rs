@@ -16,6 +16,7 @@
 extern crate libc;
 use self::libc::{c_char, c_int, c_void, size_t};
 use std::ffi::{CStr, CString};
+use std::path::Path;
 use std::str::from_utf8;
 
 #[derive(Copy, Clone)]
@@ -44,6 +45,27 @@ pub struct DBCache(pub *const c_void);
 pub struct DBFilterPolicy(pub *const c_void);
 #[derive(Copy, Clone)]
 #[repr(C)]
+pub struct DBLRUCacheOptions(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBPath(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBWalIterator(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBRateLimiter(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBWriteBufferManager(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBUniversalCompactionOptions(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBFifoCompactionOptions(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
 pub struct DBSnapshot(pub *const c_void);
 #[derive(Copy, Clone)]
 #[repr(C)]
@@ -57,15 +79,67 @@ pub struct DBWriteBatch(pub *const c_void);
 #[derive(Copy, Clone)]
 #[repr(C)]
 pub struct DBComparator(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBCompactionFilter(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBCompactionFilterFactory(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBCompactionFilterContext(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBCompactOptions(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBFlushOptions(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBHistogramData(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBCheckpoint(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBEnv(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBEnvOptions(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBSstFileWriter(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBIngestExternalFileOptions(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBTransaction(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBTransactionDB(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBTransactionDBOptions(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBTransactionOptions(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBOptimisticTransactionDB(pub *const c_void);
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct DBOptimisticTransactionOptions(pub *const c_void);
 
 pub fn new_bloom_filter(bits: c_int) -> DBFilterPolicy {
-    unsafe { rocksdb_filterpolicy_create_bloom(bits) }
+    unsafe { rocksdb_filterpolicy_create_bloom(bits as f64) }
 }
 
 pub fn new_cache(capacity: size_t) -> DBCache {
     unsafe { rocksdb_cache_create_lru(capacity) }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
 #[repr(C)]
 pub enum DBCompressionType {
     DBNoCompression = 0,
@@ -74,8 +148,11 @@ pub enum DBCompressionType {
     DBBz2Compression = 3,
     DBLz4Compression = 4,
     DBLz4hcCompression = 5,
+    DBXpressCompression = 6,
+    DBZstdCompression = 7,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
 #[repr(C)]
 pub enum DBCompactionStyle {
     DBLevelCompaction = 0,
@@ -98,14 +175,33 @@ pub fn error_message(ptr: *const i8) -> String {
     s
 }
 
+pub fn path_to_cstring(path: &Path) -> Result<CString, String> {
+    match path.to_str().map(|p| CString::new(p.as_bytes())) {
+        Some(Ok(c)) => Ok(c),
+        _ => Err(format!("Failed to convert path {:?} to CString", path)),
+    }
+}
+
 // TODO audit the use of boolean arguments, b/c I think they need to be u8
 // instead...
 #[link(name = "rocksdb")]
 extern {
     pub fn rocksdb_options_create() -> DBOptions;
     pub fn rocksdb_options_destroy(opts: DBOptions);
+    pub fn rocksdb_options_create_copy(opts: DBOptions) -> DBOptions;
     pub fn rocksdb_cache_create_lru(capacity: size_t) -> DBCache;
+    pub fn rocksdb_cache_create_lru_opts(opts: DBLRUCacheOptions) -> DBCache;
     pub fn rocksdb_cache_destroy(cache: DBCache);
+    pub fn rocksdb_cache_set_capacity(cache: DBCache, capacity: size_t);
+    pub fn rocksdb_cache_get_capacity(cache: DBCache) -> size_t;
+    pub fn rocksdb_cache_get_usage(cache: DBCache) -> size_t;
+    pub fn rocksdb_cache_get_pinned_usage(cache: DBCache) -> size_t;
+    pub fn rocksdb_lru_cache_options_create() -> DBLRUCacheOptions;
+    pub fn rocksdb_lru_cache_options_destroy(opts: DBLRUCacheOptions);
+    pub fn rocksdb_lru_cache_options_set_capacity(opts: DBLRUCacheOptions,
+                                                  capacity: size_t);
+    pub fn rocksdb_lru_cache_options_set_num_shard_bits(opts: DBLRUCacheOptions,
+                                                        num_shard_bits: c_int);
     pub fn rocksdb_block_based_options_create() -> DBBlockBasedTableOptions;
     pub fn rocksdb_block_based_options_destroy(opts: DBBlockBasedTableOptions);
     pub fn rocksdb_block_based_options_set_block_size(
@@ -129,6 +225,18 @@ extern {
         block_cache_compressed: DBCache);
     pub fn rocksdb_block_based_options_set_whole_key_filtering(
         ck_options: DBBlockBasedTableOptions, doit: bool);
+    pub fn rocksdb_block_based_options_set_format_version(
+        block_options: DBBlockBasedTableOptions,
+        format_version: c_int);
+    pub fn rocksdb_block_based_options_set_cache_index_and_filter_blocks(
+        block_options: DBBlockBasedTableOptions,
+        v: u8);
+    pub fn rocksdb_block_based_options_set_cache_index_and_filter_blocks_with_high_priority(
+        block_options: DBBlockBasedTableOptions,
+        v: u8);
+    pub fn rocksdb_block_based_options_set_pin_l0_filter_and_index_blocks_in_cache(
+        block_options: DBBlockBasedTableOptions,
+        v: u8);
     pub fn rocksdb_options_set_block_based_table_factory(
         options: DBOptions,
         block_options: DBBlockBasedTableOptions);
@@ -139,6 +247,68 @@ extern {
     pub fn rocksdb_options_set_create_if_missing(options: DBOptions, v: bool);
     pub fn rocksdb_options_set_max_open_files(options: DBOptions,
                                               files: c_int);
+    pub fn rocksdb_options_get_max_open_files(options: DBOptions) -> c_int;
+    pub fn rocksdb_options_get_write_buffer_size(options: DBOptions)
+                                                 -> size_t;
+    pub fn rocksdb_options_get_create_if_missing(options: DBOptions) -> u8;
+    pub fn rocksdb_dbpath_create(path: *const c_char,
+                                 target_size: u64)
+                                 -> DBPath;
+    pub fn rocksdb_dbpath_destroy(dbpath: DBPath);
+    pub fn rocksdb_options_set_db_paths(options: DBOptions,
+                                        path_values: *const DBPath,
+                                        num_paths: size_t);
+    pub fn rocksdb_options_set_cf_paths(options: DBOptions,
+                                        path_values: *const DBPath,
+                                        num_paths: size_t);
+    pub fn rocksdb_options_set_db_log_dir(options: DBOptions,
+                                          path: *const c_char);
+    pub fn rocksdb_options_set_wal_dir(options: DBOptions,
+                                       path: *const c_char);
+    pub fn rocksdb_options_set_WAL_ttl_seconds(options: DBOptions, ttl: u64);
+    pub fn rocksdb_options_get_WAL_ttl_seconds(options: DBOptions) -> u64;
+    pub fn rocksdb_options_set_WAL_size_limit_MB(options: DBOptions,
+                                                 limit: u64);
+    pub fn rocksdb_options_get_WAL_size_limit_MB(options: DBOptions) -> u64;
+    pub fn rocksdb_options_get_max_write_buffer_number(options: DBOptions)
+                                                       -> c_int;
+    pub fn rocksdb_options_get_min_write_buffer_number_to_merge(
+        options: DBOptions)
+        -> c_int;
+    pub fn rocksdb_options_get_target_file_size_base(options: DBOptions)
+                                                     -> u64;
+    pub fn rocksdb_options_get_num_levels(options: DBOptions) -> c_int;
+    pub fn rocksdb_options_get_level0_file_num_compaction_trigger(
+        options: DBOptions)
+        -> c_int;
+    pub fn rocksdb_options_get_level0_slowdown_writes_trigger(
+        options: DBOptions)
+        -> c_int;
+    pub fn rocksdb_options_get_level0_stop_writes_trigger(options: DBOptions)
+                                                          -> c_int;
+    pub fn rocksdb_options_get_compaction_style(options: DBOptions) -> c_int;
+    pub fn rocksdb_options_get_compression(options: DBOptions) -> c_int;
+    pub fn rocksdb_options_get_bottommost_compression(options: DBOptions)
+                                                      -> c_int;
+    pub fn rocksdb_options_get_max_background_jobs(options: DBOptions)
+                                                   -> c_int;
+    pub fn rocksdb_options_get_max_background_compactions(options: DBOptions)
+                                                          -> c_int;
+    pub fn rocksdb_options_get_max_background_flushes(options: DBOptions)
+                                                      -> c_int;
+    pub fn rocksdb_load_latest_options(db_path: *const c_char,
+                                       env: DBEnv,
+                                       ignore_unknown_options: bool,
+                                       cache: DBCache,
+                                       db_options: *mut DBOptions,
+                                       num_column_families: *mut size_t,
+                                       column_family_names: *mut *mut *mut c_char,
+                                       column_family_options: *mut *mut DBOptions,
+                                       err: *mut *const i8);
+    pub fn rocksdb_get_options_from_string(base_options: DBOptions,
+                                           opts_str: *const c_char,
+                                           new_options: DBOptions,
+                                           err: *mut *const i8);
     pub fn rocksdb_options_set_use_fsync(options: DBOptions, v: c_int);
     pub fn rocksdb_options_set_bytes_per_sync(options: DBOptions, bytes: u64);
     pub fn rocksdb_options_set_disable_data_sync(options: DBOptions,
@@ -175,6 +345,113 @@ extern {
                                                 cs: DBCompactionStyle);
     pub fn rocksdb_options_set_compression(options: DBOptions,
                                            compression_style_no: c_int);
+    pub fn rocksdb_options_set_ratelimiter(options: DBOptions,
+                                           limiter: DBRateLimiter);
+    pub fn rocksdb_ratelimiter_create(rate_bytes_per_sec: i64,
+                                      refill_period_us: i64,
+                                      fairness: i32)
+                                      -> DBRateLimiter;
+    pub fn rocksdb_ratelimiter_create_auto_tuned(rate_bytes_per_sec: i64,
+                                                 refill_period_us: i64,
+                                                 fairness: i32)
+                                                 -> DBRateLimiter;
+    pub fn rocksdb_ratelimiter_destroy(limiter: DBRateLimiter);
+    pub fn rocksdb_options_set_write_buffer_manager(
+        options: DBOptions,
+        wbm: DBWriteBufferManager);
+    pub fn rocksdb_write_buffer_manager_create(buffer_size: size_t,
+                                               allow_stall: bool)
+                                               -> DBWriteBufferManager;
+    pub fn rocksdb_write_buffer_manager_create_with_cache(
+        buffer_size: size_t,
+        cache: DBCache,
+        allow_stall: bool)
+        -> DBWriteBufferManager;
+    pub fn rocksdb_write_buffer_manager_destroy(wbm: DBWriteBufferManager);
+    pub fn rocksdb_write_buffer_manager_enabled(wbm: DBWriteBufferManager)
+                                                -> bool;
+    pub fn rocksdb_write_buffer_manager_cost_to_cache(
+        wbm: DBWriteBufferManager)
+        -> bool;
+    pub fn rocksdb_write_buffer_manager_memory_usage(
+        wbm: DBWriteBufferManager)
+        -> size_t;
+    pub fn rocksdb_write_buffer_manager_mutable_memtable_memory_usage(
+        wbm: DBWriteBufferManager)
+        -> size_t;
+    pub fn rocksdb_write_buffer_manager_dummy_entries_in_cache_usage(
+        wbm: DBWriteBufferManager)
+        -> size_t;
+    pub fn rocksdb_write_buffer_manager_buffer_size(wbm: DBWriteBufferManager)
+                                                    -> size_t;
+    pub fn rocksdb_write_buffer_manager_set_buffer_size(
+        wbm: DBWriteBufferManager,
+        new_size: size_t);
+    pub fn rocksdb_write_buffer_manager_set_allow_stall(
+        wbm: DBWriteBufferManager,
+        new_allow_stall: bool);
+    pub fn rocksdb_options_set_universal_compaction_options(
+        options: DBOptions,
+        uco: DBUniversalCompactionOptions);
+    pub fn rocksdb_options_set_fifo_compaction_options(
+        options: DBOptions,
+        fifo: DBFifoCompactionOptions);
+    pub fn rocksdb_universal_compaction_options_create()
+        -> DBUniversalCompactionOptions;
+    pub fn rocksdb_universal_compaction_options_set_size_ratio(
+        uco: DBUniversalCompactionOptions,
+        ratio: c_int);
+    pub fn rocksdb_universal_compaction_options_set_min_merge_width(
+        uco: DBUniversalCompactionOptions,
+        w: c_int);
+    pub fn rocksdb_universal_compaction_options_set_max_merge_width(
+        uco: DBUniversalCompactionOptions,
+        w: c_int);
+    pub fn rocksdb_universal_compaction_options_set_max_size_amplification_percent(
+        uco: DBUniversalCompactionOptions,
+        p: c_int);
+    pub fn rocksdb_universal_compaction_options_set_compression_size_percent(
+        uco: DBUniversalCompactionOptions,
+        p: c_int);
+    pub fn rocksdb_universal_compaction_options_set_stop_style(
+        uco: DBUniversalCompactionOptions,
+        style: DBUniversalCompactionStyle);
+    pub fn rocksdb_universal_compaction_options_destroy(
+        uco: DBUniversalCompactionOptions);
+    pub fn rocksdb_fifo_compaction_options_create() -> DBFifoCompactionOptions;
+    pub fn rocksdb_fifo_compaction_options_set_allow_compaction(
+        fifo_opts: DBFifoCompactionOptions,
+        allow_compaction: u8);
+    pub fn rocksdb_fifo_compaction_options_set_max_table_files_size(
+        fifo_opts: DBFifoCompactionOptions,
+        size: u64);
+    pub fn rocksdb_fifo_compaction_options_destroy(
+        fifo_opts: DBFifoCompactionOptions);
+    pub fn rocksdb_options_set_compression_per_level(
+        options: DBOptions,
+        level_values: *const c_int,
+        num_levels: size_t);
+    pub fn rocksdb_options_set_bottommost_compression(options: DBOptions,
+                                                      compression: c_int);
+    pub fn rocksdb_options_set_compression_options(options: DBOptions,
+                                                   window_bits: c_int,
+                                                   level: c_int,
+                                                   strategy: c_int,
+                                                   max_dict_bytes: c_int);
+    pub fn rocksdb_options_set_compression_options_zstd_max_train_bytes(
+        options: DBOptions,
+        zstd_max_train_bytes: c_int);
+    pub fn rocksdb_options_set_bottommost_compression_options(
+        options: DBOptions,
+        window_bits: c_int,
+        level: c_int,
+        strategy: c_int,
+        max_dict_bytes: c_int,
+        enabled: u8);
+    pub fn rocksdb_options_set_bottommost_compression_options_zstd_max_train_bytes(
+        options: DBOptions,
+        zstd_max_train_bytes: c_int,
+        enabled: u8);
     pub fn rocksdb_options_set_max_background_compactions(
         options: DBOptions, max_bg_compactions: c_int);
     pub fn rocksdb_options_set_max_background_flushes(options: DBOptions,
@@ -182,8 +459,10 @@ extern {
     pub fn rocksdb_options_set_filter_deletes(options: DBOptions, v: bool);
     pub fn rocksdb_options_set_disable_auto_compactions(options: DBOptions,
                                                         v: c_int);
-    pub fn rocksdb_filterpolicy_create_bloom(bits_per_key: c_int)
+    pub fn rocksdb_filterpolicy_create_bloom(bits_per_key: f64)
                                              -> DBFilterPolicy;
+    pub fn rocksdb_filterpolicy_create_bloom_full(bits_per_key: f64)
+                                                  -> DBFilterPolicy;
     pub fn rocksdb_open(options: DBOptions,
                         path: *const i8,
                         err: *mut *const i8)
@@ -311,6 +590,21 @@ extern {
                                               mo: DBMergeOperator);
     // Iterator
     pub fn rocksdb_iter_destroy(iter: DBIterator);
+    pub fn rocksdb_get_latest_sequence_number(db: DBInstance) -> u64;
+    // `options` is a rocksdb_wal_readoptions_t, which may be null for the
+    // defaults.
+    pub fn rocksdb_get_updates_since(db: DBInstance,
+                                     seq_number: u64,
+                                     options: *const c_void,
+                                     err: *mut *const i8)
+                                     -> DBWalIterator;
+    pub fn rocksdb_wal_iter_next(iter: DBWalIterator);
+    pub fn rocksdb_wal_iter_valid(iter: DBWalIterator) -> u8;
+    pub fn rocksdb_wal_iter_status(iter: DBWalIterator, err: *mut *const i8);
+    pub fn rocksdb_wal_iter_get_batch(iter: DBWalIterator,
+                                      seq: *mut u64)
+                                      -> DBWriteBatch;
+    pub fn rocksdb_wal_iter_destroy(iter: DBWalIterator);
     pub fn rocksdb_iter_valid(iter: DBIterator) -> bool;
     pub fn rocksdb_iter_seek_to_first(iter: DBIterator);
     pub fn rocksdb_iter_seek_to_last(iter: DBIterator);
@@ -387,6 +681,41 @@ extern {
     ) -> DBComparator;
     pub fn rocksdb_comparator_destroy(cmp: DBComparator);
 
+    // Compaction filter
+    pub fn rocksdb_compactionfilter_create(
+        state: *mut c_void,
+        destructor: extern "C" fn(*mut c_void) -> (),
+        filter: extern "C" fn(arg: *mut c_void,
+                          level: c_int,
+                          key: *const c_char, key_length: size_t,
+                          existing_value: *const c_char,
+                          value_length: size_t,
+                          new_value: *mut *mut c_char,
+                          new_value_length: *mut size_t,
+                          value_changed: *mut u8
+                         ) -> u8,
+        name_fn: extern "C" fn(*mut c_void) -> *const c_char
+    ) -> DBCompactionFilter;
+    pub fn rocksdb_compactionfilter_destroy(filter: DBCompactionFilter);
+    pub fn rocksdb_compactionfiltercontext_is_full_compaction(
+        context: DBCompactionFilterContext) -> bool;
+    pub fn rocksdb_compactionfiltercontext_is_manual_compaction(
+        context: DBCompactionFilterContext) -> bool;
+    pub fn rocksdb_compactionfilterfactory_create(
+        state: *mut c_void,
+        destructor: extern "C" fn(*mut c_void) -> (),
+        create_compaction_filter: extern "C" fn(
+            arg: *mut c_void,
+            context: DBCompactionFilterContext
+        ) -> DBCompactionFilter,
+        name_fn: extern "C" fn(*mut c_void) -> *const c_char
+    ) -> DBCompactionFilterFactory;
+    pub fn rocksdb_compactionfilterfactory_destroy(
+        factory: DBCompactionFilterFactory);
+    pub fn rocksdb_options_set_compaction_filter_factory(
+        options: DBOptions,
+        factory: DBCompactionFilterFactory);
+
     // Column Family
     pub fn rocksdb_open_column_families(options: DBOptions,
                                         path: *const i8,
@@ -396,6 +725,53 @@ extern {
                                         column_family_handles: *const DBCFHandle,
                                         err: *mut *const i8
                                         ) -> DBInstance;
+    pub fn rocksdb_open_for_read_only(options: DBOptions,
+                                      path: *const i8,
+                                      error_if_log_file_exist: u8,
+                                      err: *mut *const i8)
+                                      -> DBInstance;
+    pub fn rocksdb_open_for_read_only_column_families(
+        options: DBOptions,
+        path: *const i8,
+        num_column_families: c_int,
+        column_family_names: *const *const i8,
+        column_family_options: *const DBOptions,
+        column_family_handles: *const DBCFHandle,
+        error_if_log_file_exist: u8,
+        err: *mut *const i8)
+        -> DBInstance;
+    pub fn rocksdb_open_with_ttl(options: DBOptions,
+                                 path: *const i8,
+                                 ttl: c_int,
+                                 err: *mut *const i8)
+                                 -> DBInstance;
+    pub fn rocksdb_open_column_families_with_ttl(
+        options: DBOptions,
+        path: *const i8,
+        num_column_families: c_int,
+        column_family_names: *const *const i8,
+        column_family_options: *const DBOptions,
+        column_family_handles: *const DBCFHandle,
+        ttls: *const c_int,
+        err: *mut *const i8)
+        -> DBInstance;
+    pub fn rocksdb_open_as_secondary(options: DBOptions,
+                                     path: *const i8,
+                                     secondary_path: *const i8,
+                                     err: *mut *const i8)
+                                     -> DBInstance;
+    pub fn rocksdb_open_as_secondary_column_families(
+        options: DBOptions,
+        path: *const i8,
+        secondary_path: *const i8,
+        num_column_families: c_int,
+        column_family_names: *const *const i8,
+        column_family_options: *const DBOptions,
+        column_family_handles: *const DBCFHandle,
+        err: *mut *const i8)
+        -> DBInstance;
+    pub fn rocksdb_try_catch_up_with_primary(db: DBInstance,
+                                             err: *mut *const i8);
     pub fn rocksdb_create_column_family(db: DBInstance,
                                         column_family_options: DBOptions,
                                         column_family_name: *const i8,
@@ -405,7 +781,376 @@ extern {
                                       column_family_handle: DBCFHandle,
                                       err: *mut *const i8);
     pub fn rocksdb_column_family_handle_destroy(column_family_handle: DBCFHandle);
+    pub fn rocksdb_get_default_column_family_handle(db: DBInstance)
+                                                    -> DBCFHandle;
 
+    // Approximate sizes
+    pub fn rocksdb_approximate_sizes_cf_with_flags(
+        db: DBInstance,
+        column_family: DBCFHandle,
+        num_ranges: c_int,
+        range_start_key: *const *const u8,
+        range_start_key_len: *const size_t,
+        range_limit_key: *const *const u8,
+        range_limit_key_len: *const size_t,
+        include_flags: u8,
+        sizes: *mut u64,
+        err: *mut *const i8);
+
+    // Manual compaction and flush
+    pub fn rocksdb_compact_range(db: DBInstance,
+                                 start_key: *const u8,
+                                 start_key_len: size_t,
+                                 limit_key: *const u8,
+                                 limit_key_len: size_t);
+    pub fn rocksdb_compact_range_cf(db: DBInstance,
+                                    column_family: DBCFHandle,
+                                    start_key: *const u8,
+                                    start_key_len: size_t,
+                                    limit_key: *const u8,
+                                    limit_key_len: size_t);
+    pub fn rocksdb_compact_range_opt(db: DBInstance,
+                                     opt: DBCompactOptions,
+                                     start_key: *const u8,
+                                     start_key_len: size_t,
+                                     limit_key: *const u8,
+                                     limit_key_len: size_t);
+    pub fn rocksdb_compact_range_cf_opt(db: DBInstance,
+                                        column_family: DBCFHandle,
+                                        opt: DBCompactOptions,
+                                        start_key: *const u8,
+                                        start_key_len: size_t,
+                                        limit_key: *const u8,
+                                        limit_key_len: size_t);
+    pub fn rocksdb_compactoptions_create() -> DBCompactOptions;
+    pub fn rocksdb_compactoptions_destroy(opt: DBCompactOptions);
+    pub fn rocksdb_compactoptions_set_exclusive_manual_compaction(
+        opt: DBCompactOptions, v: u8);
+    pub fn rocksdb_compactoptions_set_bottommost_level_compaction(
+        opt: DBCompactOptions, v: u8);
+    pub fn rocksdb_compactoptions_set_change_level(opt: DBCompactOptions,
+                                                   v: u8);
+    pub fn rocksdb_compactoptions_set_target_level(opt: DBCompactOptions,
+                                                   n: c_int);
+    pub fn rocksdb_flushoptions_create() -> DBFlushOptions;
+    pub fn rocksdb_flushoptions_destroy(opt: DBFlushOptions);
+    pub fn rocksdb_flushoptions_set_wait(opt: DBFlushOptions, v: u8);
+    pub fn rocksdb_flush(db: DBInstance,
+                         options: DBFlushOptions,
+                         err: *mut *const i8);
+    pub fn rocksdb_flush_cf(db: DBInstance,
+                            options: DBFlushOptions,
+                            column_family: DBCFHandle,
+                            err: *mut *const i8);
+    pub fn rocksdb_flush_wal(db: DBInstance, sync: u8, err: *mut *const i8);
+
+    // Statistics
+    pub fn rocksdb_options_enable_statistics(options: DBOptions);
+    pub fn rocksdb_options_statistics_get_string(options: DBOptions)
+                                                 -> *mut c_char;
+    pub fn rocksdb_options_statistics_get_ticker_count(options: DBOptions,
+                                                       ticker_type: u32)
+                                                       -> u64;
+    pub fn rocksdb_options_statistics_get_histogram_data(
+        options: DBOptions,
+        histogram_type: u32,
+        data: DBHistogramData);
+    pub fn rocksdb_statistics_histogram_data_create() -> DBHistogramData;
+    pub fn rocksdb_statistics_histogram_data_destroy(data: DBHistogramData);
+    pub fn rocksdb_statistics_histogram_data_get_median(data: DBHistogramData)
+                                                        -> f64;
+    pub fn rocksdb_statistics_histogram_data_get_p95(data: DBHistogramData)
+                                                     -> f64;
+    pub fn rocksdb_statistics_histogram_data_get_p99(data: DBHistogramData)
+                                                     -> f64;
+    pub fn rocksdb_statistics_histogram_data_get_average(
+        data: DBHistogramData) -> f64;
+    pub fn rocksdb_statistics_histogram_data_get_std_dev(
+        data: DBHistogramData) -> f64;
+    pub fn rocksdb_statistics_histogram_data_get_max(data: DBHistogramData)
+                                                     -> f64;
+    pub fn rocksdb_statistics_histogram_data_get_min(data: DBHistogramData)
+                                                     -> f64;
+    pub fn rocksdb_statistics_histogram_data_get_count(data: DBHistogramData)
+                                                       -> u64;
+    pub fn rocksdb_statistics_histogram_data_get_sum(data: DBHistogramData)
+                                                     -> u64;
+
+    // Env
+    pub fn rocksdb_create_default_env() -> DBEnv;
+    pub fn rocksdb_create_mem_env() -> DBEnv;
+    pub fn rocksdb_env_destroy(env: DBEnv);
+    pub fn rocksdb_env_set_background_threads(env: DBEnv, n: c_int);
+    pub fn rocksdb_env_set_high_priority_background_threads(env: DBEnv,
+                                                            n: c_int);
+
+    // Checkpoint
+    pub fn rocksdb_checkpoint_object_create(db: DBInstance,
+                                            err: *mut *const i8)
+                                            -> DBCheckpoint;
+    pub fn rocksdb_checkpoint_create(checkpoint: DBCheckpoint,
+                                     checkpoint_dir: *const c_char,
+                                     log_size_for_flush: u64,
+                                     err: *mut *const i8);
+    pub fn rocksdb_checkpoint_object_destroy(checkpoint: DBCheckpoint);
+
+    // Properties
+    pub fn rocksdb_property_value(db: DBInstance,
+                                  propname: *const c_char)
+                                  -> *mut c_char;
+    pub fn rocksdb_property_value_cf(db: DBInstance,
+                                     column_family: DBCFHandle,
+                                     propname: *const c_char)
+                                     -> *mut c_char;
+    pub fn rocksdb_property_int(db: DBInstance,
+                                propname: *const c_char,
+                                out_val: *mut u64)
+                                -> c_int;
+    pub fn rocksdb_property_int_cf(db: DBInstance,
+                                   column_family: DBCFHandle,
+                                   propname: *const c_char,
+                                   out_val: *mut u64)
+                                   -> c_int;
+
+    // SST files
+    pub fn rocksdb_envoptions_create() -> DBEnvOptions;
+    pub fn rocksdb_envoptions_destroy(opt: DBEnvOptions);
+    pub fn rocksdb_sstfilewriter_create(env: DBEnvOptions,
+                                        io_options: DBOptions)
+                                        -> DBSstFileWriter;
+    pub fn rocksdb_sstfilewriter_open(writer: DBSstFileWriter,
+                                      name: *const c_char,
+                                      err: *mut *const i8);
+    pub fn rocksdb_sstfilewriter_put(writer: DBSstFileWriter,
+                                     k: *const u8,
+                                     kLen: size_t,
+                                     v: *const u8,
+                                     vLen: size_t,
+                                     err: *mut *const i8);
+    pub fn rocksdb_sstfilewriter_merge(writer: DBSstFileWriter,
+                                       k: *const u8,
+                                       kLen: size_t,
+                                       v: *const u8,
+                                       vLen: size_t,
+                                       err: *mut *const i8);
+    pub fn rocksdb_sstfilewriter_delete(writer: DBSstFileWriter,
+                                        k: *const u8,
+                                        kLen: size_t,
+                                        err: *mut *const i8);
+    pub fn rocksdb_sstfilewriter_finish(writer: DBSstFileWriter,
+                                        err: *mut *const i8);
+    pub fn rocksdb_sstfilewriter_file_size(writer: DBSstFileWriter,
+                                           file_size: *mut u64);
+    pub fn rocksdb_sstfilewriter_destroy(writer: DBSstFileWriter);
+    pub fn rocksdb_ingestexternalfileoptions_create()
+        -> DBIngestExternalFileOptions;
+    pub fn rocksdb_ingestexternalfileoptions_set_move_files(
+        opt: DBIngestExternalFileOptions,
+        move_files: u8);
+    pub fn rocksdb_ingestexternalfileoptions_set_snapshot_consistency(
+        opt: DBIngestExternalFileOptions,
+        snapshot_consistency: u8);
+    pub fn rocksdb_ingestexternalfileoptions_set_allow_global_seqno(
+        opt: DBIngestExternalFileOptions,
+        allow_global_seqno: u8);
+    pub fn rocksdb_ingestexternalfileoptions_set_allow_blocking_flush(
+        opt: DBIngestExternalFileOptions,
+        allow_blocking_flush: u8);
+    pub fn rocksdb_ingestexternalfileoptions_destroy(
+        opt: DBIngestExternalFileOptions);
+    pub fn rocksdb_ingest_external_file(db: DBInstance,
+                                        file_list: *const *const c_char,
+                                        list_len: size_t,
+                                        opt: DBIngestExternalFileOptions,
+                                        err: *mut *const i8);
+    pub fn rocksdb_ingest_external_file_cf(db: DBInstance,
+                                           handle: DBCFHandle,
+                                           file_list: *const *const c_char,
+                                           list_len: size_t,
+                                           opt: DBIngestExternalFileOptions,
+                                           err: *mut *const i8);
+
+    // Transactions
+    pub fn rocksdb_transaction_commit(txn: DBTransaction,
+                                      err: *mut *const i8);
+    pub fn rocksdb_transaction_rollback(txn: DBTransaction,
+                                        err: *mut *const i8);
+    pub fn rocksdb_transaction_destroy(txn: DBTransaction);
+    pub fn rocksdb_transaction_set_name(txn: DBTransaction,
+                                        name: *const c_char,
+                                        name_len: size_t,
+                                        err: *mut *const i8);
+    pub fn rocksdb_transaction_prepare(txn: DBTransaction,
+                                       err: *mut *const i8);
+    pub fn rocksdb_transaction_set_savepoint(txn: DBTransaction);
+    pub fn rocksdb_transaction_rollback_to_savepoint(txn: DBTransaction,
+                                                     err: *mut *const i8);
+    pub fn rocksdb_transaction_get_snapshot(txn: DBTransaction) -> DBSnapshot;
+    pub fn rocksdb_transaction_get(txn: DBTransaction,
+                                   readopts: DBReadOptions,
+                                   k: *const u8,
+                                   kLen: size_t,
+                                   valLen: *const size_t,
+                                   err: *mut *const i8)
+                                   -> *mut c_void;
+    pub fn rocksdb_transaction_get_cf(txn: DBTransaction,
+                                      readopts: DBReadOptions,
+                                      cf_handle: DBCFHandle,
+                                      k: *const u8,
+                                      kLen: size_t,
+                                      valLen: *const size_t,
+                                      err: *mut *const i8)
+                                      -> *mut c_void;
+    pub fn rocksdb_transaction_get_for_update(txn: DBTransaction,
+                                              readopts: DBReadOptions,
+                                              k: *const u8,
+                                              kLen: size_t,
+                                              valLen: *const size_t,
+                                              exclusive: u8,
+                                              err: *mut *const i8)
+                                              -> *mut c_void;
+    pub fn rocksdb_transaction_get_for_update_cf(txn: DBTransaction,
+                                                 readopts: DBReadOptions,
+                                                 cf_handle: DBCFHandle,
+                                                 k: *const u8,
+                                                 kLen: size_t,
+                                                 valLen: *const size_t,
+                                                 exclusive: u8,
+                                                 err: *mut *const i8)
+                                                 -> *mut c_void;
+    pub fn rocksdb_transaction_put(txn: DBTransaction,
+                                   k: *const u8,
+                                   kLen: size_t,
+                                   v: *const u8,
+                                   vLen: size_t,
+                                   err: *mut *const i8);
+    pub fn rocksdb_transaction_put_cf(txn: DBTransaction,
+                                      cf: DBCFHandle,
+                                      k: *const u8,
+                                      kLen: size_t,
+                                      v: *const u8,
+                                      vLen: size_t,
+                                      err: *mut *const i8);
+    pub fn rocksdb_transaction_merge(txn: DBTransaction,
+                                     k: *const u8,
+                                     kLen: size_t,
+                                     v: *const u8,
+                                     vLen: size_t,
+                                     err: *mut *const i8);
+    pub fn rocksdb_transaction_merge_cf(txn: DBTransaction,
+                                        cf: DBCFHandle,
+                                        k: *const u8,
+                                        kLen: size_t,
+                                        v: *const u8,
+                                        vLen: size_t,
+                                        err: *mut *const i8);
+    pub fn rocksdb_transaction_delete(txn: DBTransaction,
+                                      k: *const u8,
+                                      kLen: size_t,
+                                      err: *mut *const i8);
+    pub fn rocksdb_transaction_delete_cf(txn: DBTransaction,
+                                         cf: DBCFHandle,
+                                         k: *const u8,
+                                         kLen: size_t,
+                                         err: *mut *const i8);
+
+    // Pessimistic transactions
+    pub fn rocksdb_transactiondb_open_column_families(
+        options: DBOptions,
+        txn_db_options: DBTransactionDBOptions,
+        path: *const i8,
+        num_column_families: c_int,
+        column_family_names: *const *const i8,
+        column_family_options: *const DBOptions,
+        column_family_handles: *const DBCFHandle,
+        err: *mut *const i8)
+        -> DBTransactionDB;
+    pub fn rocksdb_transactiondb_close(db: DBTransactionDB);
+    pub fn rocksdb_transactiondb_get(db: DBTransactionDB,
+                                     readopts: DBReadOptions,
+                                     k: *const u8,
+                                     kLen: size_t,
+                                     valLen: *const size_t,
+                                     err: *mut *const i8)
+                                     -> *mut c_void;
+    pub fn rocksdb_transactiondb_get_cf(db: DBTransactionDB,
+                                        readopts: DBReadOptions,
+                                        cf_handle: DBCFHandle,
+                                        k: *const u8,
+                                        kLen: size_t,
+                                        valLen: *const size_t,
+                                        err: *mut *const i8)
+                                        -> *mut c_void;
+    pub fn rocksdb_transaction_begin(db: DBTransactionDB,
+                                     writeopts: DBWriteOptions,
+                                     txn_opts: DBTransactionOptions,
+                                     old_txn: DBTransaction)
+                                     -> DBTransaction;
+    pub fn rocksdb_transactiondb_options_create() -> DBTransactionDBOptions;
+    pub fn rocksdb_transactiondb_options_destroy(opts: DBTransactionDBOptions);
+    pub fn rocksdb_transactiondb_options_set_max_num_locks(
+        opts: DBTransactionDBOptions,
+        max_num_locks: i64);
+    pub fn rocksdb_transactiondb_options_set_num_stripes(
+        opts: DBTransactionDBOptions,
+        num_stripes: size_t);
+    pub fn rocksdb_transactiondb_options_set_transaction_lock_timeout(
+        opts: DBTransactionDBOptions,
+        txn_lock_timeout: i64);
+    pub fn rocksdb_transactiondb_options_set_default_lock_timeout(
+        opts: DBTransactionDBOptions,
+        default_lock_timeout: i64);
+    pub fn rocksdb_transaction_options_create() -> DBTransactionOptions;
+    pub fn rocksdb_transaction_options_destroy(opts: DBTransactionOptions);
+    pub fn rocksdb_transaction_options_set_set_snapshot(
+        opts: DBTransactionOptions,
+        v: u8);
+    pub fn rocksdb_transaction_options_set_deadlock_detect(
+        opts: DBTransactionOptions,
+        v: u8);
+    pub fn rocksdb_transaction_options_set_deadlock_detect_depth(
+        opts: DBTransactionOptions,
+        depth: i64);
+    pub fn rocksdb_transaction_options_set_lock_timeout(
+        opts: DBTransactionOptions,
+        lock_timeout: i64);
+    pub fn rocksdb_transaction_options_set_expiration(
+        opts: DBTransactionOptions,
+        expiration: i64);
+
+    // Optimistic transactions
+    pub fn rocksdb_optimistictransactiondb_open(
+        options: DBOptions,
+        path: *const i8,
+        err: *mut *const i8)
+        -> DBOptimisticTransactionDB;
+    pub fn rocksdb_optimistictransactiondb_open_column_families(
+        options: DBOptions,
+        path: *const i8,
+        num_column_families: c_int,
+        column_family_names: *const *const i8,
+        column_family_options: *const DBOptions,
+        column_family_handles: *const DBCFHandle,
+        err: *mut *const i8)
+        -> DBOptimisticTransactionDB;
+    pub fn rocksdb_optimistictransactiondb_get_base_db(
+        db: DBOptimisticTransactionDB)
+        -> DBInstance;
+    pub fn rocksdb_optimistictransactiondb_close_base_db(base_db: DBInstance);
+    pub fn rocksdb_optimistictransactiondb_close(db: DBOptimisticTransactionDB);
+    pub fn rocksdb_optimistictransaction_begin(
+        db: DBOptimisticTransactionDB,
+        writeopts: DBWriteOptions,
+        txn_opts: DBOptimisticTransactionOptions,
+        old_txn: DBTransaction)
+        -> DBTransaction;
+    pub fn rocksdb_optimistictransaction_options_create()
+        -> DBOptimisticTransactionOptions;
+    pub fn rocksdb_optimistictransaction_options_destroy(
+        opts: DBOptimisticTransactionOptions);
+    pub fn rocksdb_optimistictransaction_options_set_set_snapshot(
+        opts: DBOptimisticTransactionOptions,
+        v: u8);
 }
 
 #[test]
@@ -14,15 +14,48 @@
 // limitations under the License.
 //
 pub use ffi as rocksdb_ffi;
-pub use ffi::{DBCompactionStyle, DBComparator, new_bloom_filter};
-pub use rocksdb::{DB, DBIterator, DBVector, Direction, Writable, WriteBatch, IteratorMode};
-pub use rocksdb_options::{BlockBasedOptions, Options};
+pub use ffi::{DBCompactionStyle, DBComparator, DBCompressionType,
+              DBUniversalCompactionStyle, new_bloom_filter};
+pub use rocksdb::{BottommostLevelCompaction, ColumnFamilyDescriptor,
+                  CompactRangeOptions, DB, DBIterator, DBVector, Direction,
+                  FlushOptions, IngestExternalFileOptions, IteratorMode, Range,
+                  ReadOnlyDB, SecondaryDB, SizeApproximationFlags,
+                  WalIterator, Writable, WriteBatch};
+pub use rocksdb_options::{BlockBasedOptions, FifoCompactionOptions,
+                          FilterPolicy, Options, UniversalCompactionOptions};
 pub use merge_operator::MergeOperands;
+pub use compaction_filter::{CompactionFilter, CompactionFilterContext,
+                            CompactionFilterFactory, Decision};
+pub use properties::Property;
+pub use statistics::{Histogram, HistogramData, Statistics, Ticker};
+pub use checkpoint::Checkpoint;
+pub use env::Env;
+pub use cache::Cache;
+pub use rate_limiter::RateLimiter;
+pub use write_buffer_manager::WriteBufferManager;
 pub use backup_engine::*;
+pub use transaction::Transaction;
+pub use optimistic_transaction_db::{OptimisticTransactionDB,
+                                    OptimisticTransactionOptions};
+pub use transaction_db::{TransactionDB, TransactionDBOptions,
+                         TransactionOptions};
+pub use sst_file_writer::SstFileWriter;
 
 pub mod rocksdb;
 pub mod ffi;
 pub mod rocksdb_options;
 pub mod merge_operator;
 pub mod comparator;
+pub mod compaction_filter;
+pub mod properties;
+pub mod statistics;
 pub mod backup_engine;
+pub mod checkpoint;
+pub mod env;
+pub mod cache;
+pub mod rate_limiter;
+pub mod write_buffer_manager;
+pub mod transaction;
+pub mod optimistic_transaction_db;
+pub mod transaction_db;
+pub mod sst_file_writer;
@@ -144,8 +144,8 @@ fn main() {
 mod tests  {
     use std::thread::sleep_ms;
 
-    use rocksdb::{BlockBasedOptions, DB, MergeOperands, Options, Writable,
-                  new_bloom_filter};
+    use rocksdb::{BlockBasedOptions, DB, FilterPolicy, MergeOperands, Options,
+                  Writable};
     use rocksdb::DBCompactionStyle::DBUniversalCompaction;
 
     fn tuned_for_somebody_elses_disk(path: &str,
@@ -170,12 +170,10 @@ mod tests  {
         opts.set_max_background_flushes(4);
         opts.set_filter_deletes(false);
         blockopts.set_block_size(524288);
+        blockopts.set_filter_policy(&FilterPolicy::bloom(10.0));
         opts.set_block_based_table_factory(blockopts);
         opts.set_disable_auto_compactions(true);
 
-        let filter = new_bloom_filter(10);
-        // opts.set_filter(filter);
-
         DB::open(&opts, path).unwrap()
     }
 
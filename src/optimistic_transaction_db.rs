@@ -0,0 +1,215 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate libc;
+use self::libc::size_t;
+use std::collections::BTreeMap;
+use std::ffi::CString;
+
+use rocksdb::{ColumnFamilyDescriptor, DBVector, create_dirs,
+              open_cf_descriptors_with};
+use rocksdb_ffi::{self, DBCFHandle, error_message};
+use rocksdb_options::Options;
+use transaction::Transaction;
+
+/// A database whose transactions don't take locks. Conflicts are instead
+/// detected when a transaction commits, which then fails if another writer
+/// got to one of its keys first. This suits workloads where conflicts are
+/// rare.
+pub struct OptimisticTransactionDB {
+    inner: rocksdb_ffi::DBOptimisticTransactionDB,
+    base: rocksdb_ffi::DBInstance,
+    cfs: BTreeMap<String, DBCFHandle>,
+}
+
+unsafe impl Send for OptimisticTransactionDB {}
+unsafe impl Sync for OptimisticTransactionDB {}
+
+pub struct OptimisticTransactionOptions {
+    inner: rocksdb_ffi::DBOptimisticTransactionOptions,
+}
+
+impl OptimisticTransactionOptions {
+    pub fn new() -> OptimisticTransactionOptions {
+        let opts = unsafe {
+            rocksdb_ffi::rocksdb_optimistictransaction_options_create()
+        };
+        OptimisticTransactionOptions { inner: opts }
+    }
+
+    /// Takes a snapshot when the transaction begins. Its reads then see the
+    /// database as of that snapshot, and it conflicts with any write to its
+    /// keys made after it began rather than after they were first read.
+    pub fn set_snapshot(&mut self, snapshot: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_optimistictransaction_options_set_set_snapshot(
+                self.inner,
+                snapshot as u8);
+        }
+    }
+}
+
+impl Drop for OptimisticTransactionOptions {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_optimistictransaction_options_destroy(
+                self.inner);
+        }
+    }
+}
+
+impl OptimisticTransactionDB {
+    pub fn open(opts: &Options,
+                path: &str)
+                -> Result<OptimisticTransactionDB, String> {
+        OptimisticTransactionDB::open_cf(opts, path, &[])
+    }
+
+    /// Opens the column families `cfs`, plus "default", all configured
+    /// with `opts`.
+    pub fn open_cf(opts: &Options,
+                   path: &str,
+                   cfs: &[&str])
+                   -> Result<OptimisticTransactionDB, String> {
+        let descriptors = cfs.iter()
+                             .map(|cf| {
+                                 ColumnFamilyDescriptor::new(cf, opts.clone())
+                             })
+                             .collect();
+        OptimisticTransactionDB::open_cf_descriptors(opts, path, descriptors)
+    }
+
+    /// Opens each column family with the options in its descriptor. If
+    /// `cfs` leaves out "default", it is opened with `opts`.
+    pub fn open_cf_descriptors(opts: &Options,
+                               path: &str,
+                               cfs: Vec<ColumnFamilyDescriptor>)
+                               -> Result<OptimisticTransactionDB, String> {
+        let cpath = match CString::new(path.as_bytes()) {
+            Ok(c) => c,
+            Err(_) => return Err("Failed to convert path to CString when \
+                                  opening rocksdb"
+                                     .to_string()),
+        };
+        create_dirs(opts, path, &cfs)?;
+        let open = |num_cfs, names, cf_opts, handles, err| unsafe {
+            rocksdb_ffi::rocksdb_optimistictransactiondb_open_column_families(
+                opts.inner,
+                cpath.as_ptr(),
+                num_cfs,
+                names,
+                cf_opts,
+                handles,
+                err)
+        };
+        let (db, cf_map) = open_cf_descriptors_with(opts, cfs, open)?;
+        if db.0.is_null() {
+            return Err("Could not initialize database.".to_string());
+        }
+        let base = unsafe {
+            rocksdb_ffi::rocksdb_optimistictransactiondb_get_base_db(db)
+        };
+        Ok(OptimisticTransactionDB {
+            inner: db,
+            base: base,
+            cfs: cf_map,
+        })
+    }
+
+    pub fn cf_handle(&self, name: &str) -> Option<&DBCFHandle> {
+        self.cfs.get(name)
+    }
+
+    pub fn transaction(&self) -> Transaction {
+        self.transaction_opt(&OptimisticTransactionOptions::new())
+    }
+
+    pub fn transaction_opt(&self,
+                           txn_opts: &OptimisticTransactionOptions)
+                           -> Transaction {
+        unsafe {
+            let writeopts = rocksdb_ffi::rocksdb_writeoptions_create();
+            let txn = rocksdb_ffi::rocksdb_optimistictransaction_begin(
+                self.inner,
+                writeopts,
+                txn_opts.inner,
+                rocksdb_ffi::DBTransaction(0 as *const _));
+            rocksdb_ffi::rocksdb_writeoptions_destroy(writeopts);
+            Transaction::from_raw(txn)
+        }
+    }
+
+    /// Reads the latest committed value of `key`, outside any transaction.
+    pub fn get(&self, key: &[u8]) -> Result<Option<DBVector>, String> {
+        self.get_opt(None, key)
+    }
+
+    pub fn get_cf(&self,
+                  cf: DBCFHandle,
+                  key: &[u8])
+                  -> Result<Option<DBVector>, String> {
+        self.get_opt(Some(cf), key)
+    }
+
+    fn get_opt(&self,
+               cf: Option<DBCFHandle>,
+               key: &[u8])
+               -> Result<Option<DBVector>, String> {
+        unsafe {
+            let readopts = rocksdb_ffi::rocksdb_readoptions_create();
+            let val_len: size_t = 0;
+            let val_len_ptr = &val_len as *const size_t;
+            let mut err: *const i8 = 0 as *const i8;
+            let err_ptr: *mut *const i8 = &mut err;
+            let val = match cf {
+                Some(cf) => rocksdb_ffi::rocksdb_get_cf(self.base,
+                                                        readopts,
+                                                        cf,
+                                                        key.as_ptr(),
+                                                        key.len() as size_t,
+                                                        val_len_ptr,
+                                                        err_ptr),
+                None => rocksdb_ffi::rocksdb_get(self.base,
+                                                 readopts,
+                                                 key.as_ptr(),
+                                                 key.len() as size_t,
+                                                 val_len_ptr,
+                                                 err_ptr),
+            } as *mut u8;
+            rocksdb_ffi::rocksdb_readoptions_destroy(readopts);
+            if !err.is_null() {
+                return Err(error_message(err));
+            }
+            match val.is_null() {
+                true => Ok(None),
+                false => Ok(Some(DBVector::from_c(val, val_len))),
+            }
+        }
+    }
+}
+
+impl Drop for OptimisticTransactionDB {
+    fn drop(&mut self) {
+        unsafe {
+            for (_, cf) in self.cfs.iter() {
+                rocksdb_ffi::rocksdb_column_family_handle_destroy(*cf);
+            }
+            // Only frees the wrapper; the database itself is closed below.
+            rocksdb_ffi::rocksdb_optimistictransactiondb_close_base_db(
+                self.base);
+            rocksdb_ffi::rocksdb_optimistictransactiondb_close(self.inner);
+        }
+    }
+}
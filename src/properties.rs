@@ -0,0 +1,149 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use std::fmt;
+
+/// Well-known properties understood by `DB::property_value` and
+/// `DB::property_int_value`. Any other property can still be queried by
+/// passing its name as a string.
+pub enum Property {
+    NumFilesAtLevel(usize),
+    CompressionRatioAtLevel(usize),
+    Stats,
+    SSTables,
+    CFStats,
+    DBStats,
+    LevelStats,
+    NumImmutableMemTable,
+    NumImmutableMemTableFlushed,
+    MemTableFlushPending,
+    NumRunningFlushes,
+    CompactionPending,
+    NumRunningCompactions,
+    BackgroundErrors,
+    CurSizeActiveMemTable,
+    CurSizeAllMemTables,
+    SizeAllMemTables,
+    NumEntriesActiveMemTable,
+    NumEntriesImmMemTables,
+    NumDeletesActiveMemTable,
+    NumDeletesImmMemTables,
+    EstimateNumKeys,
+    EstimateTableReadersMem,
+    IsFileDeletionsEnabled,
+    NumSnapshots,
+    OldestSnapshotTime,
+    NumLiveVersions,
+    CurrentSuperVersionNumber,
+    EstimateLiveDataSize,
+    MinLogNumberToKeep,
+    TotalSSTFilesSize,
+    LiveSSTFilesSize,
+    BaseLevel,
+    EstimatePendingCompactionBytes,
+    AggregatedTableProperties,
+    ActualDelayedWriteRate,
+    IsWriteStopped,
+    BlockCacheCapacity,
+    BlockCacheUsage,
+    BlockCachePinnedUsage,
+}
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Property::NumFilesAtLevel(level) => {
+                return write!(f, "rocksdb.num-files-at-level{}", level)
+            }
+            Property::CompressionRatioAtLevel(level) => {
+                return write!(f, "rocksdb.compression-ratio-at-level{}", level)
+            }
+            Property::Stats => "rocksdb.stats",
+            Property::SSTables => "rocksdb.sstables",
+            Property::CFStats => "rocksdb.cfstats",
+            Property::DBStats => "rocksdb.dbstats",
+            Property::LevelStats => "rocksdb.levelstats",
+            Property::NumImmutableMemTable => "rocksdb.num-immutable-mem-table",
+            Property::NumImmutableMemTableFlushed => {
+                "rocksdb.num-immutable-mem-table-flushed"
+            }
+            Property::MemTableFlushPending => "rocksdb.mem-table-flush-pending",
+            Property::NumRunningFlushes => "rocksdb.num-running-flushes",
+            Property::CompactionPending => "rocksdb.compaction-pending",
+            Property::NumRunningCompactions => "rocksdb.num-running-compactions",
+            Property::BackgroundErrors => "rocksdb.background-errors",
+            Property::CurSizeActiveMemTable => {
+                "rocksdb.cur-size-active-mem-table"
+            }
+            Property::CurSizeAllMemTables => "rocksdb.cur-size-all-mem-tables",
+            Property::SizeAllMemTables => "rocksdb.size-all-mem-tables",
+            Property::NumEntriesActiveMemTable => {
+                "rocksdb.num-entries-active-mem-table"
+            }
+            Property::NumEntriesImmMemTables => {
+                "rocksdb.num-entries-imm-mem-tables"
+            }
+            Property::NumDeletesActiveMemTable => {
+                "rocksdb.num-deletes-active-mem-table"
+            }
+            Property::NumDeletesImmMemTables => {
+                "rocksdb.num-deletes-imm-mem-tables"
+            }
+            Property::EstimateNumKeys => "rocksdb.estimate-num-keys",
+            Property::EstimateTableReadersMem => {
+                "rocksdb.estimate-table-readers-mem"
+            }
+            Property::IsFileDeletionsEnabled => {
+                "rocksdb.is-file-deletions-enabled"
+            }
+            Property::NumSnapshots => "rocksdb.num-snapshots",
+            Property::OldestSnapshotTime => "rocksdb.oldest-snapshot-time",
+            Property::NumLiveVersions => "rocksdb.num-live-versions",
+            Property::CurrentSuperVersionNumber => {
+                "rocksdb.current-super-version-number"
+            }
+            Property::EstimateLiveDataSize => "rocksdb.estimate-live-data-size",
+            Property::MinLogNumberToKeep => "rocksdb.min-log-number-to-keep",
+            Property::TotalSSTFilesSize => "rocksdb.total-sst-files-size",
+            Property::LiveSSTFilesSize => "rocksdb.live-sst-files-size",
+            Property::BaseLevel => "rocksdb.base-level",
+            Property::EstimatePendingCompactionBytes => {
+                "rocksdb.estimate-pending-compaction-bytes"
+            }
+            Property::AggregatedTableProperties => {
+                "rocksdb.aggregated-table-properties"
+            }
+            Property::ActualDelayedWriteRate => {
+                "rocksdb.actual-delayed-write-rate"
+            }
+            Property::IsWriteStopped => "rocksdb.is-write-stopped",
+            Property::BlockCacheCapacity => "rocksdb.block-cache-capacity",
+            Property::BlockCacheUsage => "rocksdb.block-cache-usage",
+            Property::BlockCachePinnedUsage => {
+                "rocksdb.block-cache-pinned-usage"
+            }
+        };
+        f.write_str(name)
+    }
+}
+
+#[test]
+fn property_names() {
+    assert_eq!(Property::Stats.to_string(), "rocksdb.stats");
+    assert_eq!(Property::EstimateNumKeys.to_string(),
+               "rocksdb.estimate-num-keys");
+    assert_eq!(Property::NumFilesAtLevel(3).to_string(),
+               "rocksdb.num-files-at-level3");
+}
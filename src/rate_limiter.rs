@@ -0,0 +1,81 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use std::sync::Arc;
+
+use rocksdb_ffi;
+
+/// Caps the bytes per second RocksDB spends on flushes and compactions.
+/// Setting one limiter on several databases' `Options` makes them share
+/// the budget. Clones refer to the same underlying limiter.
+#[derive(Clone)]
+pub struct RateLimiter {
+    inner: Arc<RateLimiterHandle>,
+}
+
+struct RateLimiterHandle(rocksdb_ffi::DBRateLimiter);
+
+unsafe impl Send for RateLimiterHandle {}
+unsafe impl Sync for RateLimiterHandle {}
+
+impl Drop for RateLimiterHandle {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_ratelimiter_destroy(self.0);
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Allows `rate_bytes_per_sec`, handed out in slices every
+    /// `refill_period_us` microseconds. Low priority requests get through
+    /// ahead of high priority ones once in every `fairness` refills.
+    pub fn new(rate_bytes_per_sec: i64,
+               refill_period_us: i64,
+               fairness: i32)
+               -> RateLimiter {
+        RateLimiter::from_raw(unsafe {
+            rocksdb_ffi::rocksdb_ratelimiter_create(rate_bytes_per_sec,
+                                                    refill_period_us,
+                                                    fairness)
+        })
+    }
+
+    /// Like `new`, but RocksDB adjusts the rate on its own between
+    /// `rate_bytes_per_sec / 20` and `rate_bytes_per_sec`, depending on
+    /// demand.
+    pub fn new_auto_tuned(rate_bytes_per_sec: i64,
+                          refill_period_us: i64,
+                          fairness: i32)
+                          -> RateLimiter {
+        RateLimiter::from_raw(unsafe {
+            rocksdb_ffi::rocksdb_ratelimiter_create_auto_tuned(
+                rate_bytes_per_sec,
+                refill_period_us,
+                fairness)
+        })
+    }
+
+    fn from_raw(limiter: rocksdb_ffi::DBRateLimiter) -> RateLimiter {
+        if limiter.0.is_null() {
+            panic!("Could not create rocksdb rate limiter");
+        }
+        RateLimiter { inner: Arc::new(RateLimiterHandle(limiter)) }
+    }
+
+    pub fn inner(&self) -> rocksdb_ffi::DBRateLimiter {
+        self.inner.0
+    }
+}
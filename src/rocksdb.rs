@@ -19,15 +19,16 @@ extern crate libc;
 use std::collections::BTreeMap;
 use std::ffi::{CStr, CString};
 use std::fs;
-use std::io;
+use std::marker::PhantomData;
 use std::ops::Deref;
 use std::path::Path;
+use std::ptr;
 use std::slice;
 use std::str::from_utf8;
 
-use self::libc::{c_void, size_t};
+use self::libc::{c_char, c_void, size_t};
 
-use rocksdb_ffi::{self, DBCFHandle, error_message};
+use rocksdb_ffi::{self, DBCFHandle, error_message, path_to_cstring};
 use rocksdb_options::Options;
 
 pub struct DB {
@@ -38,6 +39,20 @@ pub struct DB {
 unsafe impl Send for DB {}
 unsafe impl Sync for DB {}
 
+/// A database opened with `DB::open_for_read_only`. It offers the read
+/// side of `DB` only; there is no `Writable` impl, so writes are rejected
+/// at compile time.
+pub struct ReadOnlyDB {
+    db: DB,
+}
+
+/// A database opened with `DB::open_as_secondary`. It reads like a
+/// `ReadOnlyDB` and can additionally be brought up to date with the
+/// primary.
+pub struct SecondaryDB {
+    db: ReadOnlyDB,
+}
+
 pub struct WriteBatch {
     inner: rocksdb_ffi::DBWriteBatch,
 }
@@ -183,6 +198,55 @@ impl<'a> Drop for DBIterator<'a> {
     }
 }
 
+/// The write batches committed to a database, in order, each with the
+/// sequence number of its first write. Returned by `DB::get_updates_since`.
+pub struct WalIterator<'a> {
+    db: PhantomData<&'a DB>,
+    inner: rocksdb_ffi::DBWalIterator,
+    started: bool,
+    done: bool,
+}
+
+impl<'a> Iterator for WalIterator<'a> {
+    type Item = Result<(u64, WriteBatch), String>;
+
+    fn next(&mut self) -> Option<Result<(u64, WriteBatch), String>> {
+        if self.done {
+            return None;
+        }
+        if self.started {
+            unsafe { rocksdb_ffi::rocksdb_wal_iter_next(self.inner) }
+        } else {
+            self.started = true;
+        }
+        if unsafe { rocksdb_ffi::rocksdb_wal_iter_valid(self.inner) } != 0 {
+            let mut seq: u64 = 0;
+            let batch = unsafe {
+                rocksdb_ffi::rocksdb_wal_iter_get_batch(self.inner, &mut seq)
+            };
+            return Some(Ok((seq, WriteBatch { inner: batch })));
+        }
+        // An invalid iterator is either at the end of the log or stopped
+        // on an error, such as a gap left by deleted log files.
+        self.done = true;
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe { rocksdb_ffi::rocksdb_wal_iter_status(self.inner, err_ptr) }
+        if !err.is_null() {
+            return Some(Err(error_message(err)));
+        }
+        None
+    }
+}
+
+impl<'a> Drop for WalIterator<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_wal_iter_destroy(self.inner);
+        }
+    }
+}
+
 impl <'a> Snapshot<'a> {
     pub fn new(db: &DB) -> Snapshot {
         let snapshot = unsafe {
@@ -209,6 +273,32 @@ impl <'a> Drop for Snapshot<'a> {
     }
 }
 
+/// A column family name along with the options it should be opened with.
+pub struct ColumnFamilyDescriptor {
+    pub name: String,
+    pub options: Options,
+}
+
+impl ColumnFamilyDescriptor {
+    pub fn new(name: &str, options: Options) -> ColumnFamilyDescriptor {
+        ColumnFamilyDescriptor {
+            name: name.to_string(),
+            options: options,
+        }
+    }
+}
+
+// How a database is being opened; decides which C entry point
+// open_cf_descriptors_internal ends up calling.
+enum AccessType<'a> {
+    ReadWrite,
+    ReadOnly { error_if_log_file_exist: bool },
+    Secondary { secondary_path: &'a str },
+    // `cf_ttls` lines up with the column family descriptors; `ttl` is for
+    // a "default" they leave out.
+    WithTtl { ttl: i32, cf_ttls: Vec<i32> },
+}
+
 // This is for the DB and write batches to share the same API
 pub trait Writable {
     fn put(&self, key: &[u8], value: &[u8]) -> Result<(), String>;
@@ -238,10 +328,127 @@ impl DB {
         DB::open_cf(opts, path, &[])
     }
 
+    /// Opens the column families `cfs`, plus "default", all configured
+    /// with `opts`.
     pub fn open_cf(opts: &Options,
                    path: &str,
                    cfs: &[&str])
                    -> Result<DB, String> {
+        let descriptors = cfs.iter()
+                             .map(|cf| {
+                                 ColumnFamilyDescriptor::new(cf, opts.clone())
+                             })
+                             .collect();
+        DB::open_cf_descriptors(opts, path, descriptors)
+    }
+
+    /// Opens each column family with the options in its descriptor. If
+    /// `cfs` leaves out "default", it is opened with `opts`.
+    pub fn open_cf_descriptors(opts: &Options,
+                               path: &str,
+                               cfs: Vec<ColumnFamilyDescriptor>)
+                               -> Result<DB, String> {
+        DB::open_cf_descriptors_internal(opts, path, cfs, AccessType::ReadWrite)
+    }
+
+    /// Opens the database at `path` so that entries expire `ttl` seconds
+    /// after they were written; zero or less means they never do. Expired
+    /// entries are dropped by compaction, so reads may still return them
+    /// until then. TTL databases store a timestamp with every value and
+    /// must always be opened through one of the `*_with_ttl` functions.
+    pub fn open_with_ttl(opts: &Options,
+                         path: &str,
+                         ttl: i32)
+                         -> Result<DB, String> {
+        DB::open_cf_descriptors_internal(opts,
+                                         path,
+                                         vec![],
+                                         AccessType::WithTtl {
+                                             ttl: ttl,
+                                             cf_ttls: vec![],
+                                         })
+    }
+
+    /// Like `open_with_ttl`, giving each column family in `cfs` the TTL it
+    /// is paired with. If `cfs` leaves out "default", it is opened with
+    /// `opts` and `ttl`.
+    pub fn open_cf_descriptors_with_ttl(opts: &Options,
+                                        path: &str,
+                                        ttl: i32,
+                                        cfs: Vec<(ColumnFamilyDescriptor, i32)>)
+                                        -> Result<DB, String> {
+        let (descriptors, cf_ttls) = cfs.into_iter().unzip();
+        DB::open_cf_descriptors_internal(opts,
+                                         path,
+                                         descriptors,
+                                         AccessType::WithTtl {
+                                             ttl: ttl,
+                                             cf_ttls: cf_ttls,
+                                         })
+    }
+
+    /// Opens the database at `path` without write access. Several read-only
+    /// instances may coexist with the process that owns the database; they
+    /// see its contents as of the moment they were opened. If
+    /// `error_if_log_file_exist` is set, opening fails when the WAL holds
+    /// entries that haven't been flushed yet.
+    pub fn open_for_read_only(opts: &Options,
+                              path: &str,
+                              error_if_log_file_exist: bool)
+                              -> Result<ReadOnlyDB, String> {
+        DB::open_cf_for_read_only(opts, path, &[], error_if_log_file_exist)
+    }
+
+    pub fn open_cf_for_read_only(opts: &Options,
+                                 path: &str,
+                                 cfs: &[&str],
+                                 error_if_log_file_exist: bool)
+                                 -> Result<ReadOnlyDB, String> {
+        let descriptors = cfs.iter()
+                             .map(|cf| {
+                                 ColumnFamilyDescriptor::new(cf, opts.clone())
+                             })
+                             .collect();
+        let access = AccessType::ReadOnly {
+            error_if_log_file_exist: error_if_log_file_exist,
+        };
+        DB::open_cf_descriptors_internal(opts, path, descriptors, access)
+            .map(|db| ReadOnlyDB { db: db })
+    }
+
+    /// Opens a secondary instance of the database at `primary_path`. A
+    /// secondary reads the primary's files as they are, keeping its own
+    /// info logs under `secondary_path`, and only sees newer writes after
+    /// `try_catch_up_with_primary`. RocksDB recommends setting
+    /// `max_open_files` to -1 in `opts`, otherwise the primary may delete
+    /// files the secondary still needs.
+    pub fn open_as_secondary(opts: &Options,
+                             primary_path: &str,
+                             secondary_path: &str)
+                             -> Result<SecondaryDB, String> {
+        DB::open_cf_as_secondary(opts, primary_path, secondary_path, &[])
+    }
+
+    pub fn open_cf_as_secondary(opts: &Options,
+                                primary_path: &str,
+                                secondary_path: &str,
+                                cfs: &[&str])
+                                -> Result<SecondaryDB, String> {
+        let descriptors = cfs.iter()
+                             .map(|cf| {
+                                 ColumnFamilyDescriptor::new(cf, opts.clone())
+                             })
+                             .collect();
+        let access = AccessType::Secondary { secondary_path: secondary_path };
+        DB::open_cf_descriptors_internal(opts, primary_path, descriptors, access)
+            .map(|db| SecondaryDB { db: ReadOnlyDB { db: db } })
+    }
+
+    fn open_cf_descriptors_internal(opts: &Options,
+                                    path: &str,
+                                    cfs: Vec<ColumnFamilyDescriptor>,
+                                    access: AccessType)
+                                    -> Result<DB, String> {
         let cpath = match CString::new(path.as_bytes()) {
             Ok(c) => c,
             Err(_) => return Err("Failed to convert path to CString when \
@@ -249,81 +456,116 @@ impl DB {
                                      .to_string()),
         };
         let cpath_ptr = cpath.as_ptr();
+        let csecondary_path = match access {
+            AccessType::Secondary { secondary_path } => {
+                match CString::new(secondary_path.as_bytes()) {
+                    Ok(c) => c,
+                    Err(_) => return Err("Failed to convert secondary path \
+                                          to CString when opening rocksdb"
+                                             .to_string()),
+                }
+            }
+            _ => CString::new("").unwrap(),
+        };
 
-        let ospath = Path::new(path);
-        match fs::create_dir_all(&ospath) {
-            Err(e) =>
-                return Err("Failed to create rocksdb directory.".to_string()),
-            Ok(_) => (),
+        // Only a read-write open may create the database.
+        match access {
+            AccessType::ReadWrite | AccessType::WithTtl { .. } => {
+                create_dirs(opts, path, &cfs)?;
+            }
+            _ => (),
         }
 
-        let mut err: *const i8 = 0 as *const i8;
-        let err_ptr: *mut *const i8 = &mut err;
         let db: rocksdb_ffi::DBInstance;
         let mut cfMap = BTreeMap::new();
 
         if cfs.len() == 0 {
+            let mut err: *const i8 = 0 as *const i8;
+            let err_ptr: *mut *const i8 = &mut err;
             unsafe {
-                db = rocksdb_ffi::rocksdb_open(opts.inner, cpath_ptr, err_ptr);
+                db = match access {
+                    AccessType::ReadWrite =>
+                        rocksdb_ffi::rocksdb_open(opts.inner, cpath_ptr, err_ptr),
+                    AccessType::ReadOnly { error_if_log_file_exist } =>
+                        rocksdb_ffi::rocksdb_open_for_read_only(
+                            opts.inner,
+                            cpath_ptr,
+                            error_if_log_file_exist as u8,
+                            err_ptr),
+                    AccessType::Secondary { .. } =>
+                        rocksdb_ffi::rocksdb_open_as_secondary(
+                            opts.inner,
+                            cpath_ptr,
+                            csecondary_path.as_ptr(),
+                            err_ptr),
+                    AccessType::WithTtl { ttl, .. } =>
+                        rocksdb_ffi::rocksdb_open_with_ttl(opts.inner,
+                                                           cpath_ptr,
+                                                           ttl as libc::c_int,
+                                                           err_ptr),
+                };
             }
-        } else {
-            let mut cfs_v = cfs.to_vec();
-            // Always open the default column family
-            if !cfs_v.contains(&"default") {
-                cfs_v.push("default");
-            }
-
-            // We need to store our CStrings in an intermediate vector
-            // so that their pointers remain valid.
-            let c_cfs: Vec<CString> = cfs_v.iter()
-                                           .map(|cf| {
-                                               CString::new(cf.as_bytes())
-                                                   .unwrap()
-                                           })
-                                           .collect();
-
-            let cfnames: Vec<*const i8> = c_cfs.iter()
-                                               .map(|cf| cf.as_ptr())
-                                               .collect();
-
-            // These handles will be populated by DB.
-            let mut cfhandles: Vec<rocksdb_ffi::DBCFHandle> =
-                cfs_v.iter()
-                     .map(|_| rocksdb_ffi::DBCFHandle(0 as *mut c_void))
-                     .collect();
-
-            // TODO(tyler) allow options to be passed in.
-            let cfopts: Vec<rocksdb_ffi::DBOptions> =
-                cfs_v.iter()
-                     .map(|_| unsafe { rocksdb_ffi::rocksdb_options_create() })
-                     .collect();
-
-            // Prepare to ship to C.
-            let copts: *const rocksdb_ffi::DBOptions = cfopts.as_ptr();
-            let handles: *const rocksdb_ffi::DBCFHandle = cfhandles.as_ptr();
-            let nfam = cfs_v.len();
-            unsafe {
-                db = rocksdb_ffi::rocksdb_open_column_families(opts.inner, cpath_ptr,
-                                                               nfam as libc::c_int,
-                                                               cfnames.as_ptr(),
-                                                               copts, handles, err_ptr);
+            if !err.is_null() {
+                return Err(error_message(err));
             }
-
-            for handle in cfhandles.iter() {
-                if handle.0.is_null() {
-                    return Err("Received null column family handle from DB."
-                                   .to_string());
+        } else {
+            let open = |nfam: libc::c_int,
+                        cfnames,
+                        copts,
+                        handles,
+                        err_ptr| unsafe {
+                match access {
+                    AccessType::ReadWrite =>
+                        rocksdb_ffi::rocksdb_open_column_families(
+                            opts.inner,
+                            cpath_ptr,
+                            nfam,
+                            cfnames,
+                            copts,
+                            handles,
+                            err_ptr),
+                    AccessType::ReadOnly { error_if_log_file_exist } =>
+                        rocksdb_ffi::rocksdb_open_for_read_only_column_families(
+                            opts.inner,
+                            cpath_ptr,
+                            nfam,
+                            cfnames,
+                            copts,
+                            handles,
+                            error_if_log_file_exist as u8,
+                            err_ptr),
+                    AccessType::Secondary { .. } =>
+                        rocksdb_ffi::rocksdb_open_as_secondary_column_families(
+                            opts.inner,
+                            cpath_ptr,
+                            csecondary_path.as_ptr(),
+                            nfam,
+                            cfnames,
+                            copts,
+                            handles,
+                            err_ptr),
+                    AccessType::WithTtl { ttl, ref cf_ttls } => {
+                        let mut ttls: Vec<libc::c_int> =
+                            cf_ttls.iter().map(|t| *t as libc::c_int).collect();
+                        // the implicit "default" comes last
+                        ttls.resize(nfam as usize, ttl as libc::c_int);
+                        rocksdb_ffi::rocksdb_open_column_families_with_ttl(
+                            opts.inner,
+                            cpath_ptr,
+                            nfam,
+                            cfnames,
+                            copts,
+                            handles,
+                            ttls.as_ptr(),
+                            err_ptr)
+                    }
                 }
-            }
-
-            for (n, h) in cfs_v.iter().zip(cfhandles) {
-                cfMap.insert(n.to_string(), h);
-            }
+            };
+            let (opened, handles) = open_cf_descriptors_with(opts, cfs, open)?;
+            db = opened;
+            cfMap = handles;
         }
 
-        if !err.is_null() {
-            return Err(error_message(err));
-        }
         if db.0.is_null() {
             return Err("Could not initialize database.".to_string());
         }
@@ -467,6 +709,14 @@ impl DB {
                                   opening rocksdb"
                                      .to_string()),
         };
+        for dir in opts.extra_dirs() {
+            if let Err(e) = fs::create_dir_all(dir) {
+                return Err(format!("Failed to create rocksdb directory \
+                                    {:?}: {}",
+                                   dir,
+                                   e));
+            }
+        }
         let cname_ptr = cname.as_ptr();
         let mut err: *const i8 = 0 as *const i8;
         let err_ptr: *mut *const i8 = &mut err;
@@ -522,6 +772,466 @@ impl DB {
     pub fn snapshot(&self) -> Snapshot {
         Snapshot::new(self)
     }
+
+    /// The sequence number of the most recent write.
+    pub fn latest_sequence_number(&self) -> u64 {
+        unsafe { rocksdb_ffi::rocksdb_get_latest_sequence_number(self.inner) }
+    }
+
+    /// Reads the write-ahead log from the batch containing sequence number
+    /// `seq` onwards. Batches whose log files were already deleted can't
+    /// be read, see `Options::set_wal_ttl_seconds` for keeping them.
+    pub fn get_updates_since(&self, seq: u64) -> Result<WalIterator, String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        let iter = unsafe {
+            rocksdb_ffi::rocksdb_get_updates_since(self.inner,
+                                                   seq,
+                                                   ptr::null(),
+                                                   err_ptr)
+        };
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(WalIterator {
+            db: PhantomData,
+            inner: iter,
+            started: false,
+            done: false,
+        })
+    }
+
+    /// Compacts the keys in `[start, end]`; `None` leaves that side of the
+    /// range open.
+    pub fn compact_range(&self, start: Option<&[u8]>, end: Option<&[u8]>) {
+        let (start_ptr, start_len) = opt_bytes_to_ptr(start);
+        let (end_ptr, end_len) = opt_bytes_to_ptr(end);
+        unsafe {
+            rocksdb_ffi::rocksdb_compact_range(self.inner,
+                                               start_ptr,
+                                               start_len,
+                                               end_ptr,
+                                               end_len);
+        }
+    }
+
+    pub fn compact_range_cf(&self,
+                            cf: DBCFHandle,
+                            start: Option<&[u8]>,
+                            end: Option<&[u8]>) {
+        let (start_ptr, start_len) = opt_bytes_to_ptr(start);
+        let (end_ptr, end_len) = opt_bytes_to_ptr(end);
+        unsafe {
+            rocksdb_ffi::rocksdb_compact_range_cf(self.inner,
+                                                  cf,
+                                                  start_ptr,
+                                                  start_len,
+                                                  end_ptr,
+                                                  end_len);
+        }
+    }
+
+    pub fn compact_range_opt(&self,
+                             opts: &CompactRangeOptions,
+                             start: Option<&[u8]>,
+                             end: Option<&[u8]>) {
+        let (start_ptr, start_len) = opt_bytes_to_ptr(start);
+        let (end_ptr, end_len) = opt_bytes_to_ptr(end);
+        unsafe {
+            rocksdb_ffi::rocksdb_compact_range_opt(self.inner,
+                                                   opts.inner,
+                                                   start_ptr,
+                                                   start_len,
+                                                   end_ptr,
+                                                   end_len);
+        }
+    }
+
+    pub fn compact_range_cf_opt(&self,
+                                cf: DBCFHandle,
+                                opts: &CompactRangeOptions,
+                                start: Option<&[u8]>,
+                                end: Option<&[u8]>) {
+        let (start_ptr, start_len) = opt_bytes_to_ptr(start);
+        let (end_ptr, end_len) = opt_bytes_to_ptr(end);
+        unsafe {
+            rocksdb_ffi::rocksdb_compact_range_cf_opt(self.inner,
+                                                      cf,
+                                                      opts.inner,
+                                                      start_ptr,
+                                                      start_len,
+                                                      end_ptr,
+                                                      end_len);
+        }
+    }
+
+    /// Flushes the default column family's memtable, waiting for it to
+    /// reach disk.
+    pub fn flush(&self) -> Result<(), String> {
+        self.flush_opt(&FlushOptions::new())
+    }
+
+    pub fn flush_opt(&self, opts: &FlushOptions) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_flush(self.inner, opts.inner, err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    pub fn flush_cf(&self, cf: DBCFHandle) -> Result<(), String> {
+        self.flush_cf_opt(cf, &FlushOptions::new())
+    }
+
+    pub fn flush_cf_opt(&self,
+                        cf: DBCFHandle,
+                        opts: &FlushOptions)
+                        -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_flush_cf(self.inner, opts.inner, cf, err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    /// Writes buffered WAL entries to the log file, fsyncing it if `sync`
+    /// is set.
+    pub fn flush_wal(&self, sync: bool) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_flush_wal(self.inner, sync as u8, err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    /// Adds SST files built with `SstFileWriter` to the database, placing
+    /// them as low in the LSM tree as their key ranges allow instead of
+    /// writing their contents through the memtable and WAL.
+    pub fn ingest_external_file<P: AsRef<Path>>(
+        &self,
+        paths: &[P],
+        opts: &IngestExternalFileOptions)
+        -> Result<(), String> {
+        self.ingest_external_file_opt(None, paths, opts)
+    }
+
+    pub fn ingest_external_file_cf<P: AsRef<Path>>(
+        &self,
+        cf: DBCFHandle,
+        paths: &[P],
+        opts: &IngestExternalFileOptions)
+        -> Result<(), String> {
+        self.ingest_external_file_opt(Some(cf), paths, opts)
+    }
+
+    fn ingest_external_file_opt<P: AsRef<Path>>(
+        &self,
+        cf: Option<DBCFHandle>,
+        paths: &[P],
+        opts: &IngestExternalFileOptions)
+        -> Result<(), String> {
+        let mut cpaths = Vec::with_capacity(paths.len());
+        for path in paths {
+            cpaths.push(path_to_cstring(path.as_ref())?);
+        }
+        let cpath_ptrs: Vec<*const c_char> = cpaths.iter()
+                                                   .map(|p| p.as_ptr())
+                                                   .collect();
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            match cf {
+                Some(cf) => rocksdb_ffi::rocksdb_ingest_external_file_cf(
+                    self.inner,
+                    cf,
+                    cpath_ptrs.as_ptr(),
+                    cpath_ptrs.len() as size_t,
+                    opts.inner,
+                    err_ptr),
+                None => rocksdb_ffi::rocksdb_ingest_external_file(
+                    self.inner,
+                    cpath_ptrs.as_ptr(),
+                    cpath_ptrs.len() as size_t,
+                    opts.inner,
+                    err_ptr),
+            }
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    /// Estimates the on-disk size of each of `ranges`.
+    pub fn approximate_sizes(&self,
+                             ranges: &[Range])
+                             -> Result<Vec<u64>, String> {
+        self.approximate_sizes_opt(ranges, SizeApproximationFlags::new())
+    }
+
+    pub fn approximate_sizes_cf(&self,
+                                cf: DBCFHandle,
+                                ranges: &[Range])
+                                -> Result<Vec<u64>, String> {
+        self.approximate_sizes_cf_opt(cf, ranges, SizeApproximationFlags::new())
+    }
+
+    pub fn approximate_sizes_opt(&self,
+                                 ranges: &[Range],
+                                 flags: SizeApproximationFlags)
+                                 -> Result<Vec<u64>, String> {
+        let cf = unsafe {
+            rocksdb_ffi::rocksdb_get_default_column_family_handle(self.inner)
+        };
+        let sizes = self.approximate_sizes_cf_opt(cf, ranges, flags);
+        unsafe {
+            rocksdb_ffi::rocksdb_column_family_handle_destroy(cf);
+        }
+        sizes
+    }
+
+    pub fn approximate_sizes_cf_opt(&self,
+                                    cf: DBCFHandle,
+                                    ranges: &[Range],
+                                    flags: SizeApproximationFlags)
+                                    -> Result<Vec<u64>, String> {
+        let start_keys: Vec<*const u8> = ranges.iter()
+                                               .map(|r| r.start.as_ptr())
+                                               .collect();
+        let start_lens: Vec<size_t> = ranges.iter()
+                                            .map(|r| r.start.len() as size_t)
+                                            .collect();
+        let end_keys: Vec<*const u8> = ranges.iter()
+                                             .map(|r| r.end.as_ptr())
+                                             .collect();
+        let end_lens: Vec<size_t> = ranges.iter()
+                                          .map(|r| r.end.len() as size_t)
+                                          .collect();
+        let mut sizes: Vec<u64> = vec![0; ranges.len()];
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_approximate_sizes_cf_with_flags(
+                self.inner,
+                cf,
+                ranges.len() as libc::c_int,
+                start_keys.as_ptr(),
+                start_lens.as_ptr(),
+                end_keys.as_ptr(),
+                end_lens.as_ptr(),
+                flags.bits(),
+                sizes.as_mut_ptr(),
+                err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(sizes)
+    }
+
+    /// Estimates how many bytes of `range` are still sitting in memtables.
+    /// RocksDB's C API doesn't expose the matching entry count.
+    pub fn approximate_memtable_stats(&self,
+                                      range: Range)
+                                      -> Result<u64, String> {
+        let flags = SizeApproximationFlags {
+            include_memtable: true,
+            include_files: false,
+        };
+        self.approximate_sizes_opt(&[range], flags).map(|sizes| sizes[0])
+    }
+
+    pub fn approximate_memtable_stats_cf(&self,
+                                         cf: DBCFHandle,
+                                         range: Range)
+                                         -> Result<u64, String> {
+        let flags = SizeApproximationFlags {
+            include_memtable: true,
+            include_files: false,
+        };
+        self.approximate_sizes_cf_opt(cf, &[range], flags)
+            .map(|sizes| sizes[0])
+    }
+
+    /// Returns the value of a property such as `Property::Stats` or
+    /// `"rocksdb.stats"`, or `None` if RocksDB doesn't know about it.
+    pub fn property_value<P: ToString>(&self,
+                                       name: P)
+                                       -> Result<Option<String>, String> {
+        let cname = property_to_cstring(name)?;
+        Ok(owned_c_string(unsafe {
+            rocksdb_ffi::rocksdb_property_value(self.inner, cname.as_ptr())
+        }))
+    }
+
+    pub fn property_value_cf<P: ToString>(&self,
+                                          cf: DBCFHandle,
+                                          name: P)
+                                          -> Result<Option<String>, String> {
+        let cname = property_to_cstring(name)?;
+        Ok(owned_c_string(unsafe {
+            rocksdb_ffi::rocksdb_property_value_cf(self.inner,
+                                                   cf,
+                                                   cname.as_ptr())
+        }))
+    }
+
+    /// Like `property_value`, for properties with a numeric value.
+    pub fn property_int_value<P: ToString>(&self,
+                                           name: P)
+                                           -> Result<Option<u64>, String> {
+        let cname = property_to_cstring(name)?;
+        let mut value: u64 = 0;
+        let rc = unsafe {
+            rocksdb_ffi::rocksdb_property_int(self.inner,
+                                              cname.as_ptr(),
+                                              &mut value)
+        };
+        match rc {
+            0 => Ok(Some(value)),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn property_int_value_cf<P: ToString>(&self,
+                                              cf: DBCFHandle,
+                                              name: P)
+                                              -> Result<Option<u64>, String> {
+        let cname = property_to_cstring(name)?;
+        let mut value: u64 = 0;
+        let rc = unsafe {
+            rocksdb_ffi::rocksdb_property_int_cf(self.inner,
+                                                 cf,
+                                                 cname.as_ptr(),
+                                                 &mut value)
+        };
+        match rc {
+            0 => Ok(Some(value)),
+            _ => Ok(None),
+        }
+    }
+}
+
+// Creates the database directory along with the extra directories its
+// options and those of the column families in `cfs` point at, so RocksDB,
+// which only creates the last component of each, can open them.
+pub(crate) fn create_dirs(opts: &Options,
+                          path: &str,
+                          cfs: &[ColumnFamilyDescriptor])
+                          -> Result<(), String> {
+    let mut dirs = vec![Path::new(path)];
+    dirs.extend(opts.extra_dirs());
+    for cf in cfs {
+        dirs.extend(cf.options.extra_dirs());
+    }
+    for dir in dirs {
+        if let Err(e) = fs::create_dir_all(dir) {
+            return Err(format!("Failed to create rocksdb directory {:?}: {}",
+                               dir,
+                               e));
+        }
+    }
+    Ok(())
+}
+
+// Hands the column families in `cfs` to `open`, one of the C API's
+// `*_open_column_families` functions, as the arrays of names, options and
+// handles it takes, and returns what it opened along with the handles by
+// name. If `cfs` leaves out "default", it is added last, configured with
+// `opts`.
+pub(crate) fn open_cf_descriptors_with<T, F>
+    (opts: &Options,
+     cfs: Vec<ColumnFamilyDescriptor>,
+     open: F)
+     -> Result<(T, BTreeMap<String, DBCFHandle>), String>
+    where F: FnOnce(libc::c_int,
+                    *const *const i8,
+                    *const rocksdb_ffi::DBOptions,
+                    *const DBCFHandle,
+                    *mut *const i8)
+                    -> T
+{
+    let mut cfs = cfs;
+    // Always open the default column family, configured like the database
+    // as it would be without any column families.
+    if !cfs.iter().any(|cf| cf.name == "default") {
+        cfs.push(ColumnFamilyDescriptor::new("default", opts.clone()));
+    }
+
+    // We need to store our CStrings in an intermediate vector so that their
+    // pointers remain valid.
+    let mut c_cfs: Vec<CString> = Vec::with_capacity(cfs.len());
+    for cf in &cfs {
+        match CString::new(cf.name.as_bytes()) {
+            Ok(c) => c_cfs.push(c),
+            Err(_) => return Err(format!("Failed to convert column family \
+                                          name {:?} to CString",
+                                         cf.name)),
+        }
+    }
+    let cfnames: Vec<*const i8> = c_cfs.iter().map(|cf| cf.as_ptr()).collect();
+    let cfopts: Vec<rocksdb_ffi::DBOptions> =
+        cfs.iter().map(|cf| cf.options.inner).collect();
+    // These handles will be populated by DB.
+    let cfhandles: Vec<DBCFHandle> =
+        cfs.iter().map(|_| DBCFHandle(0 as *mut c_void)).collect();
+
+    let mut err: *const i8 = 0 as *const i8;
+    let err_ptr: *mut *const i8 = &mut err;
+    let db = open(cfs.len() as libc::c_int,
+                  cfnames.as_ptr(),
+                  cfopts.as_ptr(),
+                  cfhandles.as_ptr(),
+                  err_ptr);
+    if !err.is_null() {
+        return Err(error_message(err));
+    }
+    if cfhandles.iter().any(|h| h.0.is_null()) {
+        return Err("Received null column family handle from DB.".to_string());
+    }
+    let cf_map = cfs.into_iter()
+                    .map(|cf| cf.name)
+                    .zip(cfhandles)
+                    .collect();
+    Ok((db, cf_map))
+}
+
+fn property_to_cstring<P: ToString>(name: P) -> Result<CString, String> {
+    match CString::new(name.to_string()) {
+        Ok(c) => Ok(c),
+        Err(_) => Err("Failed to convert property name to CString".to_string()),
+    }
+}
+
+// Takes ownership of a malloc'd string handed back by RocksDB.
+fn owned_c_string(ptr: *mut c_char) -> Option<String> {
+    if ptr.is_null() {
+        return None;
+    }
+    let s = unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned();
+    unsafe {
+        libc::free(ptr as *mut c_void);
+    }
+    Some(s)
+}
+
+fn opt_bytes_to_ptr(opt: Option<&[u8]>) -> (*const u8, size_t) {
+    match opt {
+        Some(v) => (v.as_ptr(), v.len() as size_t),
+        None => (0 as *const u8, 0),
+    }
 }
 
 impl Writable for DB {
@@ -659,6 +1369,15 @@ impl WriteBatch {
             inner: unsafe { rocksdb_ffi::rocksdb_writebatch_create() },
         }
     }
+
+    /// The number of writes in the batch.
+    pub fn len(&self) -> usize {
+        unsafe { rocksdb_ffi::rocksdb_writebatch_count(self.inner) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 impl Drop for WriteBatch {
@@ -678,6 +1397,88 @@ impl Drop for DB {
     }
 }
 
+impl ReadOnlyDB {
+    pub fn get(&self, key: &[u8]) -> Result<Option<DBVector>, String> {
+        self.db.get(key)
+    }
+
+    pub fn get_cf(&self,
+                  cf: DBCFHandle,
+                  key: &[u8])
+                  -> Result<Option<DBVector>, String> {
+        self.db.get_cf(cf, key)
+    }
+
+    pub fn cf_handle(&self, name: &str) -> Option<&DBCFHandle> {
+        self.db.cf_handle(name)
+    }
+
+    pub fn iterator(&self, mode: IteratorMode) -> DBIterator {
+        self.db.iterator(mode)
+    }
+
+    pub fn iterator_cf(&self,
+                       cf_handle: DBCFHandle,
+                       mode: IteratorMode)
+                       -> Result<DBIterator, String> {
+        self.db.iterator_cf(cf_handle, mode)
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        self.db.snapshot()
+    }
+
+    pub fn property_value<P: ToString>(&self,
+                                       name: P)
+                                       -> Result<Option<String>, String> {
+        self.db.property_value(name)
+    }
+
+    pub fn property_value_cf<P: ToString>(&self,
+                                          cf: DBCFHandle,
+                                          name: P)
+                                          -> Result<Option<String>, String> {
+        self.db.property_value_cf(cf, name)
+    }
+
+    pub fn property_int_value<P: ToString>(&self,
+                                           name: P)
+                                           -> Result<Option<u64>, String> {
+        self.db.property_int_value(name)
+    }
+
+    pub fn property_int_value_cf<P: ToString>(&self,
+                                              cf: DBCFHandle,
+                                              name: P)
+                                              -> Result<Option<u64>, String> {
+        self.db.property_int_value_cf(cf, name)
+    }
+}
+
+impl SecondaryDB {
+    /// Replays whatever the primary has written to its MANIFEST and WAL
+    /// since the last catch-up, making those writes visible here.
+    pub fn try_catch_up_with_primary(&self) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_try_catch_up_with_primary(self.db.db.inner,
+                                                           err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+}
+
+impl Deref for SecondaryDB {
+    type Target = ReadOnlyDB;
+    fn deref(&self) -> &ReadOnlyDB {
+        &self.db
+    }
+}
+
 impl Writable for WriteBatch {
     fn put(&self, key: &[u8], value: &[u8]) -> Result<(), String> {
         unsafe {
@@ -782,6 +1583,201 @@ impl ReadOptions {
     }
 }
 
+/// The keys from `start` (inclusive) to `end` (exclusive).
+pub struct Range<'a> {
+    start: &'a [u8],
+    end: &'a [u8],
+}
+
+impl<'a> Range<'a> {
+    pub fn new(start: &'a [u8], end: &'a [u8]) -> Range<'a> {
+        Range {
+            start: start,
+            end: end,
+        }
+    }
+}
+
+/// What `DB::approximate_sizes_opt` should count. By default only SST
+/// files are included.
+pub struct SizeApproximationFlags {
+    pub include_memtable: bool,
+    pub include_files: bool,
+}
+
+impl SizeApproximationFlags {
+    pub fn new() -> SizeApproximationFlags {
+        SizeApproximationFlags {
+            include_memtable: false,
+            include_files: true,
+        }
+    }
+
+    fn bits(&self) -> u8 {
+        let mut bits = 0;
+        if self.include_memtable {
+            bits |= 1;
+        }
+        if self.include_files {
+            bits |= 1 << 1;
+        }
+        bits
+    }
+}
+
+/// How a manual compaction treats the bottommost level.
+pub enum BottommostLevelCompaction {
+    /// Leave the bottommost level alone.
+    Skip,
+    /// Only compact it when a compaction filter is configured.
+    IfHaveCompactionFilter,
+    /// Always compact it.
+    Force,
+    /// Always compact it, but skip files created by this compaction.
+    ForceOptimized,
+}
+
+pub struct CompactRangeOptions {
+    inner: rocksdb_ffi::DBCompactOptions,
+}
+
+impl CompactRangeOptions {
+    pub fn new() -> CompactRangeOptions {
+        unsafe {
+            CompactRangeOptions {
+                inner: rocksdb_ffi::rocksdb_compactoptions_create(),
+            }
+        }
+    }
+
+    pub fn set_exclusive_manual_compaction(&mut self, v: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_compactoptions_set_exclusive_manual_compaction(
+                self.inner, v as u8);
+        }
+    }
+
+    pub fn set_bottommost_level_compaction(&mut self,
+                                           v: BottommostLevelCompaction) {
+        unsafe {
+            rocksdb_ffi::rocksdb_compactoptions_set_bottommost_level_compaction(
+                self.inner, v as u8);
+        }
+    }
+
+    /// Moves the compacted files to `target_level` (see
+    /// `set_target_level`) once the compaction is done.
+    pub fn set_change_level(&mut self, v: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_compactoptions_set_change_level(self.inner,
+                                                                 v as u8);
+        }
+    }
+
+    pub fn set_target_level(&mut self, level: i32) {
+        unsafe {
+            rocksdb_ffi::rocksdb_compactoptions_set_target_level(self.inner,
+                                                                 level);
+        }
+    }
+}
+
+impl Drop for CompactRangeOptions {
+    fn drop(&mut self) {
+        unsafe { rocksdb_ffi::rocksdb_compactoptions_destroy(self.inner) }
+    }
+}
+
+pub struct FlushOptions {
+    inner: rocksdb_ffi::DBFlushOptions,
+}
+
+impl FlushOptions {
+    pub fn new() -> FlushOptions {
+        unsafe {
+            FlushOptions { inner: rocksdb_ffi::rocksdb_flushoptions_create() }
+        }
+    }
+
+    /// Whether a flush blocks until the memtable has been written out.
+    /// Defaults to true.
+    pub fn set_wait(&mut self, wait: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_flushoptions_set_wait(self.inner, wait as u8);
+        }
+    }
+}
+
+impl Drop for FlushOptions {
+    fn drop(&mut self) {
+        unsafe { rocksdb_ffi::rocksdb_flushoptions_destroy(self.inner) }
+    }
+}
+
+pub struct IngestExternalFileOptions {
+    inner: rocksdb_ffi::DBIngestExternalFileOptions,
+}
+
+impl IngestExternalFileOptions {
+    pub fn new() -> IngestExternalFileOptions {
+        unsafe {
+            IngestExternalFileOptions {
+                inner: rocksdb_ffi::rocksdb_ingestexternalfileoptions_create(),
+            }
+        }
+    }
+
+    /// Moves the files into the database instead of copying them. Defaults
+    /// to false.
+    pub fn set_move_files(&mut self, move_files: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_ingestexternalfileoptions_set_move_files(
+                self.inner,
+                move_files as u8);
+        }
+    }
+
+    /// Keeps snapshots taken before the ingestion from seeing the ingested
+    /// keys. Defaults to true.
+    pub fn set_snapshot_consistency(&mut self, snapshot_consistency: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_ingestexternalfileoptions_set_snapshot_consistency(
+                self.inner,
+                snapshot_consistency as u8);
+        }
+    }
+
+    /// Allows ingesting files whose keys overlap existing data, by giving
+    /// them a sequence number newer than that data. When false, such an
+    /// ingestion fails. Defaults to true.
+    pub fn set_allow_global_seqno(&mut self, allow_global_seqno: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_ingestexternalfileoptions_set_allow_global_seqno(
+                self.inner,
+                allow_global_seqno as u8);
+        }
+    }
+
+    /// Lets the ingestion flush the memtable first, blocking writes while
+    /// it does, if the memtable overlaps the files. When false, such an
+    /// ingestion fails. Defaults to true.
+    pub fn set_allow_blocking_flush(&mut self, allow_blocking_flush: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_ingestexternalfileoptions_set_allow_blocking_flush(
+                self.inner,
+                allow_blocking_flush as u8);
+        }
+    }
+}
+
+impl Drop for IngestExternalFileOptions {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_ingestexternalfileoptions_destroy(self.inner)
+        }
+    }
+}
+
 pub struct DBVector {
     base: *mut u8,
     len: usize,
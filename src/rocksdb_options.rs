@@ -14,14 +14,28 @@
 // limitations under the License.
 //
 extern crate libc;
-use self::libc::{c_int, size_t};
-use std::ffi::CString;
+use self::libc::{c_char, c_int, c_void, size_t};
+use std::ffi::{CStr, CString};
+use std::fmt;
 use std::mem;
+use std::path::{Path, PathBuf};
+use std::ptr;
 
-use rocksdb_ffi;
+use rocksdb_ffi::{self, error_message, path_to_cstring};
+use rocksdb::ColumnFamilyDescriptor;
+use env::Env;
 use merge_operator::{self, MergeOperands, MergeOperatorCallback,
                      full_merge_callback, partial_merge_callback};
+use statistics::Statistics;
+use cache::Cache;
+use rate_limiter::RateLimiter;
+use write_buffer_manager::WriteBufferManager;
 use comparator::{self, ComparatorCallback, compare_callback};
+use compaction_filter::{CloneFactory, CompactionFilter,
+                        CompactionFilterFactory,
+                        CompactionFilterFactoryCallback,
+                        create_filter_callback, factory_destructor_callback,
+                        factory_name_callback};
 
 pub struct BlockBasedOptions {
     inner: rocksdb_ffi::DBBlockBasedTableOptions,
@@ -29,6 +43,13 @@ pub struct BlockBasedOptions {
 
 pub struct Options {
     pub inner: rocksdb_ffi::DBOptions,
+    // The C API can't read paths back, so remember the ones given to the
+    // setters to create the directories before opening. Paths coming from
+    // `from_string` or `load_latest` aren't tracked.
+    wal_dir: Option<PathBuf>,
+    db_log_dir: Option<PathBuf>,
+    db_paths: Vec<PathBuf>,
+    cf_paths: Vec<PathBuf>,
 }
 
 impl Drop for Options {
@@ -39,6 +60,95 @@ impl Drop for Options {
     }
 }
 
+impl Clone for Options {
+    fn clone(&self) -> Options {
+        let opts = unsafe {
+            rocksdb_ffi::rocksdb_options_create_copy(self.inner)
+        };
+        Options {
+            inner: opts,
+            wal_dir: self.wal_dir.clone(),
+            db_log_dir: self.db_log_dir.clone(),
+            db_paths: self.db_paths.clone(),
+            cf_paths: self.cf_paths.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for Options {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Options")
+            .field("create_if_missing", &self.get_create_if_missing())
+            .field("write_buffer_size", &self.get_write_buffer_size())
+            .field("max_write_buffer_number",
+                   &self.get_max_write_buffer_number())
+            .field("min_write_buffer_number_to_merge",
+                   &self.get_min_write_buffer_number_to_merge())
+            .field("max_open_files", &self.get_max_open_files())
+            .field("target_file_size_base", &self.get_target_file_size_base())
+            .field("num_levels", &self.get_num_levels())
+            .field("level_zero_file_num_compaction_trigger",
+                   &self.get_level_zero_file_num_compaction_trigger())
+            .field("level_zero_slowdown_writes_trigger",
+                   &self.get_level_zero_slowdown_writes_trigger())
+            .field("level_zero_stop_writes_trigger",
+                   &self.get_level_zero_stop_writes_trigger())
+            .field("compaction_style", &self.get_compaction_style())
+            .field("compression_type", &self.get_compression_type())
+            .field("bottommost_compression",
+                   &self.get_bottommost_compression())
+            .field("max_background_jobs", &self.get_max_background_jobs())
+            .field("max_background_compactions",
+                   &self.get_max_background_compactions())
+            .field("max_background_flushes",
+                   &self.get_max_background_flushes())
+            .finish()
+    }
+}
+
+fn set_paths<P: AsRef<Path>>(options: rocksdb_ffi::DBOptions,
+                             paths: &[(P, u64)],
+                             set: unsafe extern "C" fn(rocksdb_ffi::DBOptions,
+                                                       *const rocksdb_ffi::DBPath,
+                                                       size_t))
+                             -> Result<(), String> {
+    let mut cpaths: Vec<CString> = Vec::with_capacity(paths.len());
+    for p in paths {
+        cpaths.push(path_to_cstring(p.0.as_ref())?);
+    }
+    unsafe {
+        let dbpaths: Vec<rocksdb_ffi::DBPath> =
+            cpaths.iter()
+                  .zip(paths.iter())
+                  .map(|(cpath, p)| {
+                      rocksdb_ffi::rocksdb_dbpath_create(cpath.as_ptr(), p.1)
+                  })
+                  .collect();
+        // the paths are copied into the options
+        set(options, dbpaths.as_ptr(), dbpaths.len() as size_t);
+        for dbpath in dbpaths {
+            rocksdb_ffi::rocksdb_dbpath_destroy(dbpath);
+        }
+    }
+    Ok(())
+}
+
+fn compression_type_from_raw(t: c_int)
+                             -> Option<rocksdb_ffi::DBCompressionType> {
+    use rocksdb_ffi::DBCompressionType::*;
+    match t {
+        0 => Some(DBNoCompression),
+        1 => Some(DBSnappyCompression),
+        2 => Some(DBZlibCompression),
+        3 => Some(DBBz2Compression),
+        4 => Some(DBLz4Compression),
+        5 => Some(DBLz4hcCompression),
+        6 => Some(DBXpressCompression),
+        7 => Some(DBZstdCompression),
+        _ => None,
+    }
+}
+
 impl Drop for BlockBasedOptions {
     fn drop(&mut self) {
         unsafe {
@@ -65,31 +175,273 @@ impl BlockBasedOptions {
                                                                     size);
         }
     }
+
+    /// Closes a block early once it is within this percentage of the block
+    /// size and the next entry would push it past the block size.
+    pub fn set_block_size_deviation(&mut self, deviation: i32) {
+        unsafe {
+            rocksdb_ffi::rocksdb_block_based_options_set_block_size_deviation(
+                self.inner,
+                deviation);
+        }
+    }
+
+    /// How many keys go between restart points for delta encoding of keys.
+    pub fn set_block_restart_interval(&mut self, interval: i32) {
+        unsafe {
+            rocksdb_ffi::rocksdb_block_based_options_set_block_restart_interval(
+                self.inner,
+                interval);
+        }
+    }
+
+    pub fn set_filter_policy(&mut self, policy: &FilterPolicy) {
+        unsafe {
+            // RocksDB takes ownership of the policy it is handed.
+            rocksdb_ffi::rocksdb_block_based_options_set_filter_policy(
+                self.inner,
+                policy.create());
+        }
+    }
+
+    /// Uses `cache` for uncompressed blocks instead of a cache of its own.
+    /// Every table using these options holds a reference to the cache, so
+    /// it may be dropped on the Rust side at any time.
+    pub fn set_block_cache(&mut self, cache: &Cache) {
+        unsafe {
+            rocksdb_ffi::rocksdb_block_based_options_set_block_cache(
+                self.inner,
+                cache.inner());
+        }
+    }
+
+    /// Whether filters cover whole keys, as opposed to only prefixes.
+    /// Defaults to true.
+    pub fn set_whole_key_filtering(&mut self, whole_key_filtering: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_block_based_options_set_whole_key_filtering(
+                self.inner,
+                whole_key_filtering);
+        }
+    }
+
+    /// Keeps index and filter blocks in the block cache, where they count
+    /// against its capacity, rather than in table reader memory.
+    pub fn set_cache_index_and_filter_blocks(&mut self, v: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_block_based_options_set_cache_index_and_filter_blocks(
+                self.inner,
+                v as u8);
+        }
+    }
+
+    /// With `set_cache_index_and_filter_blocks`, puts index and filter
+    /// blocks in the block cache's high-priority pool so data blocks are
+    /// evicted first.
+    pub fn set_cache_index_and_filter_blocks_with_high_priority(&mut self,
+                                                                v: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_block_based_options_set_cache_index_and_filter_blocks_with_high_priority(
+                self.inner,
+                v as u8);
+        }
+    }
+
+    /// With `set_cache_index_and_filter_blocks`, keeps the index and filter
+    /// blocks of level 0 files pinned in the block cache.
+    pub fn set_pin_l0_filter_and_index_blocks_in_cache(&mut self, v: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_block_based_options_set_pin_l0_filter_and_index_blocks_in_cache(
+                self.inner,
+                v as u8);
+        }
+    }
+
+    /// The on-disk format version of new tables. Newer versions can't be
+    /// read by older RocksDB releases.
+    pub fn set_format_version(&mut self, version: i32) {
+        unsafe {
+            rocksdb_ffi::rocksdb_block_based_options_set_format_version(
+                self.inner,
+                version);
+        }
+    }
+}
+
+/// Tuning for `DBUniversalCompaction`, set with
+/// `Options::set_universal_compaction_options`.
+pub struct UniversalCompactionOptions {
+    inner: rocksdb_ffi::DBUniversalCompactionOptions,
+}
+
+impl Drop for UniversalCompactionOptions {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_universal_compaction_options_destroy(
+                self.inner);
+        }
+    }
+}
+
+impl UniversalCompactionOptions {
+    pub fn new() -> UniversalCompactionOptions {
+        let opts = unsafe {
+            rocksdb_ffi::rocksdb_universal_compaction_options_create()
+        };
+        UniversalCompactionOptions { inner: opts }
+    }
+
+    /// How much bigger, in percent, a run may be than the total size of
+    /// the younger runs and still be picked for the same compaction.
+    pub fn set_size_ratio(&mut self, ratio: c_int) {
+        unsafe {
+            rocksdb_ffi::rocksdb_universal_compaction_options_set_size_ratio(
+                self.inner,
+                ratio);
+        }
+    }
+
+    /// The fewest sorted runs a single compaction merges.
+    pub fn set_min_merge_width(&mut self, width: c_int) {
+        unsafe {
+            rocksdb_ffi::rocksdb_universal_compaction_options_set_min_merge_width(
+                self.inner,
+                width);
+        }
+    }
+
+    /// The most sorted runs a single compaction merges.
+    pub fn set_max_merge_width(&mut self, width: c_int) {
+        unsafe {
+            rocksdb_ffi::rocksdb_universal_compaction_options_set_max_merge_width(
+                self.inner,
+                width);
+        }
+    }
+
+    /// How much extra space, as a percentage of the oldest run's size, the
+    /// younger runs may take up before everything is compacted into one
+    /// run.
+    pub fn set_max_size_amplification_percent(&mut self, percent: c_int) {
+        unsafe {
+            rocksdb_ffi::rocksdb_universal_compaction_options_set_max_size_amplification_percent(
+                self.inner,
+                percent);
+        }
+    }
+
+    /// Compresses only the oldest runs making up this percentage of the
+    /// data; -1 compresses everything as the compression options say.
+    pub fn set_compression_size_percent(&mut self, percent: c_int) {
+        unsafe {
+            rocksdb_ffi::rocksdb_universal_compaction_options_set_compression_size_percent(
+                self.inner,
+                percent);
+        }
+    }
+
+    /// How the runs to merge are picked: stop at the first run that is
+    /// too big compared to the next one, or compare against the total
+    /// size picked so far.
+    pub fn set_stop_style(&mut self,
+                          style: rocksdb_ffi::DBUniversalCompactionStyle) {
+        unsafe {
+            rocksdb_ffi::rocksdb_universal_compaction_options_set_stop_style(
+                self.inner,
+                style);
+        }
+    }
+}
+
+/// Tuning for `DBFifoCompaction`, set with
+/// `Options::set_fifo_compaction_options`.
+pub struct FifoCompactionOptions {
+    inner: rocksdb_ffi::DBFifoCompactionOptions,
+}
+
+impl Drop for FifoCompactionOptions {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_fifo_compaction_options_destroy(self.inner);
+        }
+    }
 }
 
-// TODO figure out how to create these in a Rusty way
-// /pub fn set_filter(&mut self, filter: rocksdb_ffi::DBFilterPolicy) {
-// /    unsafe {
-// /        rocksdb_ffi::rocksdb_block_based_options_set_filter_policy(
-// /            self.inner, filter);
-// /    }
-// /}
-
-/// /pub fn set_cache(&mut self, cache: rocksdb_ffi::DBCache) {
-/// /    unsafe {
-/// /        rocksdb_ffi::rocksdb_block_based_options_set_block_cache(
-/// /            self.inner, cache);
-/// /    }
-/// /}
-
-/// /pub fn set_cache_compressed(&mut self, cache: rocksdb_ffi::DBCache) {
-/// /    unsafe {
-/// /        rocksdb_ffi::
-/// rocksdb_block_based_options_set_block_cache_compressed(
-/// /            self.inner, cache);
-/// /    }
-/// /}
+impl FifoCompactionOptions {
+    pub fn new() -> FifoCompactionOptions {
+        let opts = unsafe {
+            rocksdb_ffi::rocksdb_fifo_compaction_options_create()
+        };
+        FifoCompactionOptions { inner: opts }
+    }
+
+    /// Once the table files add up to more than this many bytes, the
+    /// oldest ones are deleted.
+    pub fn set_max_table_files_size(&mut self, nbytes: u64) {
+        unsafe {
+            rocksdb_ffi::rocksdb_fifo_compaction_options_set_max_table_files_size(
+                self.inner,
+                nbytes);
+        }
+    }
 
+    /// Also merges small level 0 files into bigger ones, instead of only
+    /// ever deleting files.
+    pub fn set_allow_compaction(&mut self, allow: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_fifo_compaction_options_set_allow_compaction(
+                self.inner,
+                allow as u8);
+        }
+    }
+}
+
+enum FilterKind {
+    Bloom,
+    BloomFull,
+}
+
+/// Describes a filter for `BlockBasedOptions::set_filter_policy`, which
+/// lets reads skip tables that can't contain a key. Every table options it
+/// is set on gets its own instance, so one `FilterPolicy` can be used for
+/// any number of databases and column families.
+pub struct FilterPolicy {
+    kind: FilterKind,
+    bits_per_key: f64,
+}
+
+impl FilterPolicy {
+    /// A bloom filter using about `bits_per_key` bits per key; 10 gives
+    /// roughly a 1% false positive rate.
+    pub fn bloom(bits_per_key: f64) -> FilterPolicy {
+        FilterPolicy {
+            kind: FilterKind::Bloom,
+            bits_per_key: bits_per_key,
+        }
+    }
+
+    /// Same as `bloom`. RocksDB no longer writes the original block-based
+    /// filter format, so both build the same full filter.
+    pub fn bloom_full(bits_per_key: f64) -> FilterPolicy {
+        FilterPolicy {
+            kind: FilterKind::BloomFull,
+            bits_per_key: bits_per_key,
+        }
+    }
+
+    fn create(&self) -> rocksdb_ffi::DBFilterPolicy {
+        unsafe {
+            match self.kind {
+                FilterKind::Bloom =>
+                    rocksdb_ffi::rocksdb_filterpolicy_create_bloom(
+                        self.bits_per_key),
+                FilterKind::BloomFull =>
+                    rocksdb_ffi::rocksdb_filterpolicy_create_bloom_full(
+                        self.bits_per_key),
+            }
+        }
+    }
+}
 
 impl Options {
     pub fn new() -> Options {
@@ -99,7 +451,224 @@ impl Options {
             if opt_ptr.is_null() {
                 panic!("Could not create rocksdb options".to_string());
             }
-            Options { inner: opts }
+            Options::from_raw(opts)
+        }
+    }
+
+    fn from_raw(opts: rocksdb_ffi::DBOptions) -> Options {
+        Options {
+            inner: opts,
+            wal_dir: None,
+            db_log_dir: None,
+            db_paths: Vec::new(),
+            cf_paths: Vec::new(),
+        }
+    }
+
+    /// Reads the newest `OPTIONS-*` file RocksDB wrote for the database at
+    /// `path`, returning its database-wide options along with a descriptor
+    /// for every column family, ready for `DB::open_cf_descriptors`.
+    /// Directories the file points at, such as `wal_dir` or `db_paths`,
+    /// must already exist, as only RocksDB creates them, and it only
+    /// creates their last component.
+    ///
+    /// The column families using block-based tables share a new 32 MiB LRU
+    /// block cache created for this load; use `load_latest_opt` to pass a
+    /// `Cache` shared with other databases instead.
+    pub fn load_latest<P: AsRef<Path>>
+        (path: P)
+         -> Result<(Options, Vec<ColumnFamilyDescriptor>), String> {
+        Options::load_latest_opt(path,
+                                 &Env::new(),
+                                 false,
+                                 &Cache::new_lru_cache(32 << 20))
+    }
+
+    /// Like `load_latest`. Options RocksDB doesn't recognise are skipped
+    /// rather than failing the load when `ignore_unknown_options` is set,
+    /// and `cache` becomes the block cache of every column family using
+    /// block-based tables.
+    pub fn load_latest_opt<P: AsRef<Path>>
+        (path: P,
+         env: &Env,
+         ignore_unknown_options: bool,
+         cache: &Cache)
+         -> Result<(Options, Vec<ColumnFamilyDescriptor>), String> {
+        let cpath = path_to_cstring(path.as_ref())?;
+        let mut db_options = rocksdb_ffi::DBOptions(ptr::null());
+        let mut num_cfs: size_t = 0;
+        let mut cf_names: *mut *mut c_char = ptr::null_mut();
+        let mut cf_options: *mut rocksdb_ffi::DBOptions = ptr::null_mut();
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_load_latest_options(cpath.as_ptr(),
+                                                     env.inner(),
+                                                     ignore_unknown_options,
+                                                     cache.inner(),
+                                                     &mut db_options,
+                                                     &mut num_cfs,
+                                                     &mut cf_names,
+                                                     &mut cf_options,
+                                                     err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        // The options objects are adopted as they are; only the arrays
+        // and the names are ours to free.
+        let mut cfs = Vec::with_capacity(num_cfs as usize);
+        unsafe {
+            for i in 0..num_cfs as isize {
+                let name_ptr = *cf_names.offset(i);
+                let name = CStr::from_ptr(name_ptr).to_string_lossy().into_owned();
+                libc::free(name_ptr as *mut c_void);
+                let options = Options::from_raw(*cf_options.offset(i));
+                cfs.push(ColumnFamilyDescriptor::new(&name, options));
+            }
+            libc::free(cf_names as *mut c_void);
+            libc::free(cf_options as *mut c_void);
+        }
+        Ok((Options::from_raw(db_options), cfs))
+    }
+
+    /// Builds new options by applying `opts_str`, a list of RocksDB option
+    /// assignments such as `"write_buffer_size=4194304;max_open_files=100"`,
+    /// on top of `base`. Directories set this way, such as `wal_dir` or
+    /// `db_paths`, must already exist up to their last component, unlike
+    /// those given to `set_wal_dir` and friends.
+    pub fn from_string(base: &Options, opts_str: &str) -> Result<Options, String> {
+        let c_opts = match CString::new(opts_str.as_bytes()) {
+            Ok(c) => c,
+            Err(_) => {
+                return Err("Failed to convert options string to CString"
+                    .to_string())
+            }
+        };
+        let mut options = Options::new();
+        options.wal_dir = base.wal_dir.clone();
+        options.db_log_dir = base.db_log_dir.clone();
+        options.db_paths = base.db_paths.clone();
+        options.cf_paths = base.cf_paths.clone();
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_get_options_from_string(base.inner,
+                                                         c_opts.as_ptr(),
+                                                         options.inner,
+                                                         err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(options)
+    }
+
+    pub fn get_create_if_missing(&self) -> bool {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_get_create_if_missing(self.inner) != 0
+        }
+    }
+
+    pub fn get_write_buffer_size(&self) -> usize {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_get_write_buffer_size(self.inner) as usize
+        }
+    }
+
+    pub fn get_max_write_buffer_number(&self) -> c_int {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_get_max_write_buffer_number(self.inner)
+        }
+    }
+
+    pub fn get_min_write_buffer_number_to_merge(&self) -> c_int {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_get_min_write_buffer_number_to_merge(
+                self.inner)
+        }
+    }
+
+    pub fn get_max_open_files(&self) -> c_int {
+        unsafe { rocksdb_ffi::rocksdb_options_get_max_open_files(self.inner) }
+    }
+
+    pub fn get_target_file_size_base(&self) -> u64 {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_get_target_file_size_base(self.inner)
+        }
+    }
+
+    pub fn get_num_levels(&self) -> c_int {
+        unsafe { rocksdb_ffi::rocksdb_options_get_num_levels(self.inner) }
+    }
+
+    pub fn get_level_zero_file_num_compaction_trigger(&self) -> c_int {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_get_level0_file_num_compaction_trigger(
+                self.inner)
+        }
+    }
+
+    pub fn get_level_zero_slowdown_writes_trigger(&self) -> c_int {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_get_level0_slowdown_writes_trigger(
+                self.inner)
+        }
+    }
+
+    pub fn get_level_zero_stop_writes_trigger(&self) -> c_int {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_get_level0_stop_writes_trigger(
+                self.inner)
+        }
+    }
+
+    pub fn get_compaction_style(&self) -> rocksdb_ffi::DBCompactionStyle {
+        let style = unsafe {
+            rocksdb_ffi::rocksdb_options_get_compaction_style(self.inner)
+        };
+        match style {
+            1 => rocksdb_ffi::DBCompactionStyle::DBUniversalCompaction,
+            2 => rocksdb_ffi::DBCompactionStyle::DBFifoCompaction,
+            _ => rocksdb_ffi::DBCompactionStyle::DBLevelCompaction,
+        }
+    }
+
+    /// The compression used for levels `set_compression_per_level` doesn't
+    /// cover. `None` if it is a codec this crate has no name for.
+    pub fn get_compression_type(&self)
+                                -> Option<rocksdb_ffi::DBCompressionType> {
+        compression_type_from_raw(unsafe {
+            rocksdb_ffi::rocksdb_options_get_compression(self.inner)
+        })
+    }
+
+    /// `None` until `set_bottommost_compression` is called; until then the
+    /// bottommost level is compressed like the others.
+    pub fn get_bottommost_compression(&self)
+                                      -> Option<rocksdb_ffi::DBCompressionType> {
+        compression_type_from_raw(unsafe {
+            rocksdb_ffi::rocksdb_options_get_bottommost_compression(self.inner)
+        })
+    }
+
+    pub fn get_max_background_jobs(&self) -> c_int {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_get_max_background_jobs(self.inner)
+        }
+    }
+
+    pub fn get_max_background_compactions(&self) -> c_int {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_get_max_background_compactions(
+                self.inner)
+        }
+    }
+
+    pub fn get_max_background_flushes(&self) -> c_int {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_get_max_background_flushes(self.inner)
         }
     }
 
@@ -118,6 +687,95 @@ impl Options {
         }
     }
 
+    /// Keeps the write-ahead log in `path` instead of the database
+    /// directory, e.g. to put it on faster storage than the table files.
+    pub fn set_wal_dir<P: AsRef<Path>>(&mut self,
+                                       path: P)
+                                       -> Result<(), String> {
+        let cpath = path_to_cstring(path.as_ref())?;
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_wal_dir(self.inner, cpath.as_ptr());
+        }
+        self.wal_dir = Some(path.as_ref().to_path_buf());
+        Ok(())
+    }
+
+    /// Moves write-ahead log files to an archive once their contents are
+    /// flushed, and deletes archived files older than `ttl` seconds, so
+    /// `DB::get_updates_since` can still read them in the meantime. Zero,
+    /// with a zero `set_wal_size_limit_mb`, deletes them straight away.
+    pub fn set_wal_ttl_seconds(&mut self, ttl: u64) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_WAL_ttl_seconds(self.inner, ttl);
+        }
+    }
+
+    pub fn get_wal_ttl_seconds(&self) -> u64 {
+        unsafe { rocksdb_ffi::rocksdb_options_get_WAL_ttl_seconds(self.inner) }
+    }
+
+    /// Like `set_wal_ttl_seconds`, but deletes the oldest archived files
+    /// once the archive grows past `limit` megabytes.
+    pub fn set_wal_size_limit_mb(&mut self, limit: u64) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_WAL_size_limit_MB(self.inner,
+                                                               limit);
+        }
+    }
+
+    pub fn get_wal_size_limit_mb(&self) -> u64 {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_get_WAL_size_limit_MB(self.inner)
+        }
+    }
+
+    /// Writes the info log to `path` instead of the database directory.
+    pub fn set_db_log_dir<P: AsRef<Path>>(&mut self,
+                                          path: P)
+                                          -> Result<(), String> {
+        let cpath = path_to_cstring(path.as_ref())?;
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_db_log_dir(self.inner,
+                                                        cpath.as_ptr());
+        }
+        self.db_log_dir = Some(path.as_ref().to_path_buf());
+        Ok(())
+    }
+
+    /// Spreads table files over several directories, each given with the
+    /// number of bytes it should hold. Newer, smaller data fills the
+    /// earlier paths first, and the last path takes whatever doesn't fit.
+    pub fn set_db_paths<P: AsRef<Path>>(&mut self,
+                                        paths: &[(P, u64)])
+                                        -> Result<(), String> {
+        set_paths(self.inner, paths, rocksdb_ffi::rocksdb_options_set_db_paths)?;
+        self.db_paths = paths.iter().map(|p| p.0.as_ref().to_path_buf()).collect();
+        Ok(())
+    }
+
+    /// Like `set_db_paths`, for the table files of a single column family.
+    /// Only has an effect on the options a column family is opened or
+    /// created with.
+    pub fn set_cf_paths<P: AsRef<Path>>(&mut self,
+                                        paths: &[(P, u64)])
+                                        -> Result<(), String> {
+        set_paths(self.inner, paths, rocksdb_ffi::rocksdb_options_set_cf_paths)?;
+        self.cf_paths = paths.iter().map(|p| p.0.as_ref().to_path_buf()).collect();
+        Ok(())
+    }
+
+    // The directories besides the database's own that the setters above
+    // put files in, which have to exist before the database is opened.
+    pub(crate) fn extra_dirs(&self) -> Vec<&Path> {
+        self.wal_dir
+            .iter()
+            .chain(self.db_log_dir.iter())
+            .chain(self.db_paths.iter())
+            .chain(self.cf_paths.iter())
+            .map(|p| p.as_path())
+            .collect()
+    }
+
     pub fn create_if_missing(&mut self, create_if_missing: bool) {
         unsafe {
             rocksdb_ffi::rocksdb_options_set_create_if_missing(
@@ -163,6 +821,42 @@ impl Options {
         }
     }
 
+    /// Runs `filter` over every key/value pair rewritten by a compaction.
+    /// Each compaction works on its own clone of `filter`.
+    pub fn set_compaction_filter<F>(&mut self, name: &str, filter: F)
+        where F: CompactionFilter + Clone + Send + Sync + 'static
+    {
+        self.set_compaction_filter_factory(name, CloneFactory(filter));
+    }
+
+    pub fn set_compaction_filter_factory<F>(&mut self, name: &str, factory: F)
+        where F: CompactionFilterFactory + Send + Sync + 'static,
+              F::Filter: Send + 'static
+    {
+        let cb = Box::new(CompactionFilterFactoryCallback {
+            name: CString::new(name.as_bytes()).unwrap(),
+            factory: factory,
+        });
+
+        unsafe {
+            let cff = rocksdb_ffi::rocksdb_compactionfilterfactory_create(
+                Box::into_raw(cb) as *mut c_void,
+                factory_destructor_callback::<F>,
+                create_filter_callback::<F>,
+                factory_name_callback::<F>);
+            rocksdb_ffi::rocksdb_options_set_compaction_filter_factory(
+                self.inner, cff);
+        }
+    }
+
+    /// Starts collecting statistics for DBs opened with these options. The
+    /// returned handle can be queried while they run.
+    pub fn enable_statistics(&mut self) -> Statistics {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_enable_statistics(self.inner);
+        }
+        Statistics::new(self.inner)
+    }
 
     pub fn set_block_cache_size_mb(&mut self, cache_size: u64) {
         unsafe {
@@ -235,6 +929,15 @@ impl Options {
         }
     }
 
+    /// Counts this database's memtables against `wbm`'s memory limit,
+    /// along with everything else it is set on.
+    pub fn set_write_buffer_manager(&mut self, wbm: &WriteBufferManager) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_write_buffer_manager(self.inner,
+                                                                  wbm.inner());
+        }
+    }
+
     pub fn set_target_file_size_base(&mut self, size: u64) {
         unsafe {
             rocksdb_ffi::rocksdb_options_set_target_file_size_base(self.inner,
@@ -271,6 +974,121 @@ impl Options {
         }
     }
 
+    /// Copies in the options used when the compaction style is
+    /// `DBUniversalCompaction`.
+    pub fn set_universal_compaction_options(&mut self,
+                                            uco: &UniversalCompactionOptions) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_universal_compaction_options(
+                self.inner,
+                uco.inner);
+        }
+    }
+
+    /// Copies in the options used when the compaction style is
+    /// `DBFifoCompaction`.
+    pub fn set_fifo_compaction_options(&mut self,
+                                       fifo: &FifoCompactionOptions) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_fifo_compaction_options(self.inner,
+                                                                     fifo.inner);
+        }
+    }
+
+    /// Sets the compression used for all levels, unless overridden by
+    /// `set_compression_per_level` or `set_bottommost_compression`.
+    /// Opening a database fails if the codec wasn't compiled into RocksDB.
+    pub fn set_compression_type(&mut self,
+                                t: rocksdb_ffi::DBCompressionType) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_compression(self.inner,
+                                                         t as c_int);
+        }
+    }
+
+    /// Sets the compression of each level, starting at level 0. Levels
+    /// past the end of `level_types` use the last entry.
+    pub fn set_compression_per_level(
+        &mut self,
+        level_types: &[rocksdb_ffi::DBCompressionType]) {
+        let level_types: Vec<c_int> = level_types.iter()
+                                                 .map(|t| *t as c_int)
+                                                 .collect();
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_compression_per_level(
+                self.inner,
+                level_types.as_ptr(),
+                level_types.len() as size_t);
+        }
+    }
+
+    /// Sets the compression of the bottommost level, which usually holds
+    /// most of the data and is rewritten least often.
+    pub fn set_bottommost_compression(&mut self,
+                                      t: rocksdb_ffi::DBCompressionType) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_bottommost_compression(self.inner,
+                                                                    t as c_int);
+        }
+    }
+
+    /// Tunes the codec: `window_bits`, `level` and `strategy` are passed on
+    /// to codecs that take them (zlib, zstd, lz4hc). A non-zero
+    /// `max_dict_bytes` builds a compression dictionary of up to that many
+    /// bytes per SST file, for codecs that support one.
+    pub fn set_compression_options(&mut self,
+                                   window_bits: c_int,
+                                   level: c_int,
+                                   strategy: c_int,
+                                   max_dict_bytes: c_int) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_compression_options(
+                self.inner,
+                window_bits,
+                level,
+                strategy,
+                max_dict_bytes);
+        }
+    }
+
+    /// With zstd and a dictionary, samples up to this many bytes to train
+    /// the dictionary instead of using raw samples.
+    pub fn set_zstd_max_train_bytes(&mut self, value: c_int) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_compression_options_zstd_max_train_bytes(
+                self.inner,
+                value);
+        }
+    }
+
+    /// Like `set_compression_options`, for the bottommost level. These only
+    /// take effect once set, until then the bottommost level uses the
+    /// general compression options.
+    pub fn set_bottommost_compression_options(&mut self,
+                                              window_bits: c_int,
+                                              level: c_int,
+                                              strategy: c_int,
+                                              max_dict_bytes: c_int) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_bottommost_compression_options(
+                self.inner,
+                window_bits,
+                level,
+                strategy,
+                max_dict_bytes,
+                1);
+        }
+    }
+
+    pub fn set_bottommost_zstd_max_train_bytes(&mut self, value: c_int) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_bottommost_compression_options_zstd_max_train_bytes(
+                self.inner,
+                value,
+                1);
+        }
+    }
+
     pub fn set_max_background_compactions(&mut self, n: c_int) {
         unsafe {
             rocksdb_ffi::rocksdb_options_set_max_background_compactions(
@@ -285,6 +1103,27 @@ impl Options {
         }
     }
 
+    /// Limits flush and compaction I/O with a new `RateLimiter` that only
+    /// this `Options` uses. See `RateLimiter::new` for the arguments.
+    pub fn set_ratelimiter(&mut self,
+                           rate_bytes_per_sec: i64,
+                           refill_period_us: i64,
+                           fairness: i32) {
+        let limiter = RateLimiter::new(rate_bytes_per_sec,
+                                       refill_period_us,
+                                       fairness);
+        self.set_rate_limiter(&limiter);
+    }
+
+    /// Limits flush and compaction I/O with `limiter`, sharing it with
+    /// everything else it is set on.
+    pub fn set_rate_limiter(&mut self, limiter: &RateLimiter) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_set_ratelimiter(self.inner,
+                                                         limiter.inner());
+        }
+    }
+
     pub fn set_filter_deletes(&mut self, filter: bool) {
         unsafe {
             rocksdb_ffi::rocksdb_options_set_filter_deletes(self.inner, filter);
@@ -0,0 +1,146 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate libc;
+use self::libc::size_t;
+use std::marker::PhantomData;
+use std::path::Path;
+
+use rocksdb_ffi::{self, error_message, path_to_cstring};
+use rocksdb_options::Options;
+
+/// Builds an SST file outside of any database, for bulk loading with
+/// `DB::ingest_external_file`. Keys must be added in strictly increasing
+/// order according to the comparator in the `Options` the writer was
+/// created with, which must match the one of the column family the file
+/// is ingested into.
+pub struct SstFileWriter<'a> {
+    inner: rocksdb_ffi::DBSstFileWriter,
+    opts: PhantomData<&'a Options>,
+}
+
+impl<'a> SstFileWriter<'a> {
+    pub fn create(opts: &'a Options) -> SstFileWriter<'a> {
+        let writer = unsafe {
+            // the writer keeps its own copy of the env options
+            let env_opts = rocksdb_ffi::rocksdb_envoptions_create();
+            let writer = rocksdb_ffi::rocksdb_sstfilewriter_create(env_opts,
+                                                                   opts.inner);
+            rocksdb_ffi::rocksdb_envoptions_destroy(env_opts);
+            writer
+        };
+        SstFileWriter {
+            inner: writer,
+            opts: PhantomData,
+        }
+    }
+
+    /// Starts writing a new file at `path`.
+    pub fn open<P: AsRef<Path>>(&mut self, path: P) -> Result<(), String> {
+        let cpath = path_to_cstring(path.as_ref())?;
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_sstfilewriter_open(self.inner,
+                                                    cpath.as_ptr(),
+                                                    err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    pub fn put(&mut self, key: &[u8], value: &[u8]) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_sstfilewriter_put(self.inner,
+                                                   key.as_ptr(),
+                                                   key.len() as size_t,
+                                                   value.as_ptr(),
+                                                   value.len() as size_t,
+                                                   err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    pub fn merge(&mut self, key: &[u8], value: &[u8]) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_sstfilewriter_merge(self.inner,
+                                                     key.as_ptr(),
+                                                     key.len() as size_t,
+                                                     value.as_ptr(),
+                                                     value.len() as size_t,
+                                                     err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    pub fn delete(&mut self, key: &[u8]) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_sstfilewriter_delete(self.inner,
+                                                      key.as_ptr(),
+                                                      key.len() as size_t,
+                                                      err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    /// Completes the file. It is only usable, and only ingestible, once
+    /// this has succeeded. Fails if nothing was added to the file.
+    pub fn finish(&mut self) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_sstfilewriter_finish(self.inner, err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    /// The size in bytes of the file being written so far.
+    pub fn file_size(&self) -> u64 {
+        let mut file_size: u64 = 0;
+        unsafe {
+            rocksdb_ffi::rocksdb_sstfilewriter_file_size(self.inner,
+                                                         &mut file_size);
+        }
+        file_size
+    }
+}
+
+impl<'a> Drop for SstFileWriter<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_sstfilewriter_destroy(self.inner);
+        }
+    }
+}
@@ -0,0 +1,971 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate libc;
+use std::ffi::CStr;
+use std::fmt;
+
+use rocksdb_ffi;
+
+/// A handle on the statistics collected by a DB opened with
+/// `Options::enable_statistics`. It stays usable after the DB is closed.
+pub struct Statistics {
+    // RocksDB only exposes statistics through an options object, so we hold
+    // a copy of the options that shares the original's statistics.
+    inner: rocksdb_ffi::DBOptions,
+}
+
+unsafe impl Send for Statistics {}
+unsafe impl Sync for Statistics {}
+
+pub struct HistogramData {
+    pub median: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub average: f64,
+    pub std_dev: f64,
+    pub max: f64,
+    pub min: f64,
+    pub count: u64,
+    pub sum: u64,
+}
+
+impl Statistics {
+    pub(crate) fn new(opts: rocksdb_ffi::DBOptions) -> Statistics {
+        Statistics {
+            inner: unsafe { rocksdb_ffi::rocksdb_options_create_copy(opts) },
+        }
+    }
+
+    pub fn get_ticker_count(&self, ticker: Ticker) -> u64 {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_statistics_get_ticker_count(
+                self.inner, ticker as u32)
+        }
+    }
+
+    pub fn get_histogram_data(&self, histogram: Histogram) -> HistogramData {
+        unsafe {
+            let data = rocksdb_ffi::rocksdb_statistics_histogram_data_create();
+            rocksdb_ffi::rocksdb_options_statistics_get_histogram_data(
+                self.inner, histogram as u32, data);
+            let rv = HistogramData {
+                median:
+                    rocksdb_ffi::rocksdb_statistics_histogram_data_get_median(
+                        data),
+                p95: rocksdb_ffi::rocksdb_statistics_histogram_data_get_p95(
+                    data),
+                p99: rocksdb_ffi::rocksdb_statistics_histogram_data_get_p99(
+                    data),
+                average:
+                    rocksdb_ffi::rocksdb_statistics_histogram_data_get_average(
+                        data),
+                std_dev:
+                    rocksdb_ffi::rocksdb_statistics_histogram_data_get_std_dev(
+                        data),
+                max: rocksdb_ffi::rocksdb_statistics_histogram_data_get_max(
+                    data),
+                min: rocksdb_ffi::rocksdb_statistics_histogram_data_get_min(
+                    data),
+                count:
+                    rocksdb_ffi::rocksdb_statistics_histogram_data_get_count(
+                        data),
+                sum: rocksdb_ffi::rocksdb_statistics_histogram_data_get_sum(
+                    data),
+            };
+            rocksdb_ffi::rocksdb_statistics_histogram_data_destroy(data);
+            rv
+        }
+    }
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        unsafe {
+            let ptr = rocksdb_ffi::rocksdb_options_statistics_get_string(
+                self.inner);
+            if ptr.is_null() {
+                return Ok(());
+            }
+            let rv = f.write_str(&CStr::from_ptr(ptr).to_string_lossy());
+            libc::free(ptr as *mut libc::c_void);
+            rv
+        }
+    }
+}
+
+impl Drop for Statistics {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_options_destroy(self.inner);
+        }
+    }
+}
+
+// Mirrors `enum Tickers` in rocksdb/statistics.h. The discriminants are
+// handed straight to RocksDB, so the order has to match the linked library.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum Ticker {
+    BlockCacheMiss,
+    BlockCacheHit,
+    BlockCacheAdd,
+    BlockCacheAddFailures,
+    BlockCacheIndexMiss,
+    BlockCacheIndexHit,
+    BlockCacheIndexAdd,
+    BlockCacheIndexBytesInsert,
+    BlockCacheFilterMiss,
+    BlockCacheFilterHit,
+    BlockCacheFilterAdd,
+    BlockCacheFilterBytesInsert,
+    BlockCacheDataMiss,
+    BlockCacheDataHit,
+    BlockCacheDataAdd,
+    BlockCacheDataBytesInsert,
+    BlockCacheBytesRead,
+    BlockCacheBytesWrite,
+    BlockCacheCompressionDictMiss,
+    BlockCacheCompressionDictHit,
+    BlockCacheCompressionDictAdd,
+    BlockCacheCompressionDictBytesInsert,
+    BlockCacheAddRedundant,
+    BlockCacheIndexAddRedundant,
+    BlockCacheFilterAddRedundant,
+    BlockCacheDataAddRedundant,
+    BlockCacheCompressionDictAddRedundant,
+    SecondaryCacheHits,
+    SecondaryCacheFilterHits,
+    SecondaryCacheIndexHits,
+    SecondaryCacheDataHits,
+    CompressedSecondaryCacheDummyHits,
+    CompressedSecondaryCacheHits,
+    CompressedSecondaryCachePromotions,
+    CompressedSecondaryCachePromotionSkips,
+    BloomFilterUseful,
+    BloomFilterFullPositive,
+    BloomFilterFullTruePositive,
+    BloomFilterPrefixChecked,
+    BloomFilterPrefixUseful,
+    BloomFilterPrefixTruePositive,
+    PersistentCacheHit,
+    PersistentCacheMiss,
+    SimBlockCacheHit,
+    SimBlockCacheMiss,
+    MemtableHit,
+    MemtableMiss,
+    GetHitL0,
+    GetHitL1,
+    GetHitL2AndUp,
+    CompactionKeyDropNewerEntry,
+    CompactionKeyDropObsolete,
+    CompactionKeyDropRangeDel,
+    CompactionKeyDropUser,
+    CompactionRangeDelDropObsolete,
+    CompactionOptimizedDelDropObsolete,
+    CompactionCancelled,
+    NumberKeysWritten,
+    NumberKeysRead,
+    NumberKeysUpdated,
+    BytesWritten,
+    BytesRead,
+    NumberDbSeek,
+    NumberDbNext,
+    NumberDbPrev,
+    NumberDbSeekFound,
+    NumberDbNextFound,
+    NumberDbPrevFound,
+    IterBytesRead,
+    NumberIterSkip,
+    NumberOfReseeksInIteration,
+    NoIteratorCreated,
+    NoIteratorDeleted,
+    NoFileOpens,
+    NoFileErrors,
+    StallMicros,
+    DbMutexWaitMicros,
+    NumberMultigetCalls,
+    NumberMultigetKeysRead,
+    NumberMultigetBytesRead,
+    NumberMultigetKeysFound,
+    NumberMergeFailures,
+    GetUpdatesSinceCalls,
+    WalFileSynced,
+    WalFileBytes,
+    WriteDoneBySelf,
+    WriteDoneByOther,
+    WriteWithWal,
+    CompactReadBytes,
+    CompactWriteBytes,
+    FlushWriteBytes,
+    CompactReadBytesMarked,
+    CompactReadBytesPeriodic,
+    CompactReadBytesTtl,
+    CompactWriteBytesMarked,
+    CompactWriteBytesPeriodic,
+    CompactWriteBytesTtl,
+    NumberDirectLoadTableProperties,
+    NumberSuperversionAcquires,
+    NumberSuperversionReleases,
+    NumberSuperversionCleanups,
+    NumberBlockCompressed,
+    NumberBlockDecompressed,
+    BytesCompressedFrom,
+    BytesCompressedTo,
+    BytesCompressionBypassed,
+    BytesCompressionRejected,
+    NumberBlockCompressionBypassed,
+    NumberBlockCompressionRejected,
+    BytesDecompressedFrom,
+    BytesDecompressedTo,
+    MergeOperationTotalTime,
+    FilterOperationTotalTime,
+    CompactionCpuTotalTime,
+    RowCacheHit,
+    RowCacheMiss,
+    ReadAmpEstimateUsefulBytes,
+    ReadAmpTotalReadBytes,
+    NumberRateLimiterDrains,
+    BlobDbNumPut,
+    BlobDbNumWrite,
+    BlobDbNumGet,
+    BlobDbNumMultiget,
+    BlobDbNumSeek,
+    BlobDbNumNext,
+    BlobDbNumPrev,
+    BlobDbNumKeysWritten,
+    BlobDbNumKeysRead,
+    BlobDbBytesWritten,
+    BlobDbBytesRead,
+    BlobDbWriteInlined,
+    BlobDbWriteInlinedTtl,
+    BlobDbWriteBlob,
+    BlobDbWriteBlobTtl,
+    BlobDbBlobFileBytesWritten,
+    BlobDbBlobFileBytesRead,
+    BlobDbBlobFileSynced,
+    BlobDbBlobIndexExpiredCount,
+    BlobDbBlobIndexExpiredSize,
+    BlobDbBlobIndexEvictedCount,
+    BlobDbBlobIndexEvictedSize,
+    BlobDbGcNumFiles,
+    BlobDbGcNumNewFiles,
+    BlobDbGcFailures,
+    BlobDbGcNumKeysRelocated,
+    BlobDbGcBytesRelocated,
+    BlobDbFifoNumFilesEvicted,
+    BlobDbFifoNumKeysEvicted,
+    BlobDbFifoBytesEvicted,
+    BlobDbCacheMiss,
+    BlobDbCacheHit,
+    BlobDbCacheAdd,
+    BlobDbCacheAddFailures,
+    BlobDbCacheBytesRead,
+    BlobDbCacheBytesWrite,
+    TxnPrepareMutexOverhead,
+    TxnOldCommitMapMutexOverhead,
+    TxnDuplicateKeyOverhead,
+    TxnSnapshotMutexOverhead,
+    TxnGetTryAgain,
+    FilesMarkedTrash,
+    FilesDeletedFromTrashQueue,
+    FilesDeletedImmediately,
+    ErrorHandlerBgErrorCount,
+    ErrorHandlerBgIoErrorCount,
+    ErrorHandlerBgRetryableIoErrorCount,
+    ErrorHandlerAutoresumeCount,
+    ErrorHandlerAutoresumeRetryTotalCount,
+    ErrorHandlerAutoresumeSuccessCount,
+    MemtablePayloadBytesAtFlush,
+    MemtableGarbageBytesAtFlush,
+    VerifyChecksumReadBytes,
+    BackupReadBytes,
+    BackupWriteBytes,
+    RemoteCompactReadBytes,
+    RemoteCompactWriteBytes,
+    HotFileReadBytes,
+    WarmFileReadBytes,
+    ColdFileReadBytes,
+    HotFileReadCount,
+    WarmFileReadCount,
+    ColdFileReadCount,
+    LastLevelReadBytes,
+    LastLevelReadCount,
+    NonLastLevelReadBytes,
+    NonLastLevelReadCount,
+    LastLevelSeekFiltered,
+    LastLevelSeekFilterMatch,
+    LastLevelSeekData,
+    LastLevelSeekDataUsefulNoFilter,
+    LastLevelSeekDataUsefulFilterMatch,
+    NonLastLevelSeekFiltered,
+    NonLastLevelSeekFilterMatch,
+    NonLastLevelSeekData,
+    NonLastLevelSeekDataUsefulNoFilter,
+    NonLastLevelSeekDataUsefulFilterMatch,
+    BlockChecksumComputeCount,
+    BlockChecksumMismatchCount,
+    MultigetCoroutineCount,
+    ReadAsyncMicros,
+    AsyncReadErrorCount,
+    TableOpenPrefetchTailMiss,
+    TableOpenPrefetchTailHit,
+    TimestampFilterTableChecked,
+    TimestampFilterTableFiltered,
+    ReadaheadTrimmed,
+    FifoMaxSizeCompactions,
+    FifoTtlCompactions,
+    FifoChangeTemperatureCompactions,
+    PrefetchBytes,
+    PrefetchBytesUseful,
+    PrefetchHits,
+    SstFooterCorruptionCount,
+    FileReadCorruptionRetryCount,
+    FileReadCorruptionRetrySuccessCount,
+    NumberWbwiIngest,
+}
+
+impl Ticker {
+    /// The name RocksDB uses for this ticker in its statistics dump.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Ticker::BlockCacheMiss => "rocksdb.block.cache.miss",
+            Ticker::BlockCacheHit => "rocksdb.block.cache.hit",
+            Ticker::BlockCacheAdd => "rocksdb.block.cache.add",
+            Ticker::BlockCacheAddFailures => "rocksdb.block.cache.add.failures",
+            Ticker::BlockCacheIndexMiss => "rocksdb.block.cache.index.miss",
+            Ticker::BlockCacheIndexHit => "rocksdb.block.cache.index.hit",
+            Ticker::BlockCacheIndexAdd => "rocksdb.block.cache.index.add",
+            Ticker::BlockCacheIndexBytesInsert => {
+                "rocksdb.block.cache.index.bytes.insert"
+            }
+            Ticker::BlockCacheFilterMiss => "rocksdb.block.cache.filter.miss",
+            Ticker::BlockCacheFilterHit => "rocksdb.block.cache.filter.hit",
+            Ticker::BlockCacheFilterAdd => "rocksdb.block.cache.filter.add",
+            Ticker::BlockCacheFilterBytesInsert => {
+                "rocksdb.block.cache.filter.bytes.insert"
+            }
+            Ticker::BlockCacheDataMiss => "rocksdb.block.cache.data.miss",
+            Ticker::BlockCacheDataHit => "rocksdb.block.cache.data.hit",
+            Ticker::BlockCacheDataAdd => "rocksdb.block.cache.data.add",
+            Ticker::BlockCacheDataBytesInsert => {
+                "rocksdb.block.cache.data.bytes.insert"
+            }
+            Ticker::BlockCacheBytesRead => "rocksdb.block.cache.bytes.read",
+            Ticker::BlockCacheBytesWrite => "rocksdb.block.cache.bytes.write",
+            Ticker::BlockCacheCompressionDictMiss => {
+                "rocksdb.block.cache.compression.dict.miss"
+            }
+            Ticker::BlockCacheCompressionDictHit => {
+                "rocksdb.block.cache.compression.dict.hit"
+            }
+            Ticker::BlockCacheCompressionDictAdd => {
+                "rocksdb.block.cache.compression.dict.add"
+            }
+            Ticker::BlockCacheCompressionDictBytesInsert => {
+                "rocksdb.block.cache.compression.dict.bytes.insert"
+            }
+            Ticker::BlockCacheAddRedundant => {
+                "rocksdb.block.cache.add.redundant"
+            }
+            Ticker::BlockCacheIndexAddRedundant => {
+                "rocksdb.block.cache.index.add.redundant"
+            }
+            Ticker::BlockCacheFilterAddRedundant => {
+                "rocksdb.block.cache.filter.add.redundant"
+            }
+            Ticker::BlockCacheDataAddRedundant => {
+                "rocksdb.block.cache.data.add.redundant"
+            }
+            Ticker::BlockCacheCompressionDictAddRedundant => {
+                "rocksdb.block.cache.compression.dict.add.redundant"
+            }
+            Ticker::SecondaryCacheHits => "rocksdb.secondary.cache.hits",
+            Ticker::SecondaryCacheFilterHits => {
+                "rocksdb.secondary.cache.filter.hits"
+            }
+            Ticker::SecondaryCacheIndexHits => {
+                "rocksdb.secondary.cache.index.hits"
+            }
+            Ticker::SecondaryCacheDataHits => {
+                "rocksdb.secondary.cache.data.hits"
+            }
+            Ticker::CompressedSecondaryCacheDummyHits => {
+                "rocksdb.compressed.secondary.cache.dummy.hits"
+            }
+            Ticker::CompressedSecondaryCacheHits => {
+                "rocksdb.compressed.secondary.cache.hits"
+            }
+            Ticker::CompressedSecondaryCachePromotions => {
+                "rocksdb.compressed.secondary.cache.promotions"
+            }
+            Ticker::CompressedSecondaryCachePromotionSkips => {
+                "rocksdb.compressed.secondary.cache.promotion.skips"
+            }
+            Ticker::BloomFilterUseful => "rocksdb.bloom.filter.useful",
+            Ticker::BloomFilterFullPositive => {
+                "rocksdb.bloom.filter.full.positive"
+            }
+            Ticker::BloomFilterFullTruePositive => {
+                "rocksdb.bloom.filter.full.true.positive"
+            }
+            Ticker::BloomFilterPrefixChecked => {
+                "rocksdb.bloom.filter.prefix.checked"
+            }
+            Ticker::BloomFilterPrefixUseful => {
+                "rocksdb.bloom.filter.prefix.useful"
+            }
+            Ticker::BloomFilterPrefixTruePositive => {
+                "rocksdb.bloom.filter.prefix.true.positive"
+            }
+            Ticker::PersistentCacheHit => "rocksdb.persistent.cache.hit",
+            Ticker::PersistentCacheMiss => "rocksdb.persistent.cache.miss",
+            Ticker::SimBlockCacheHit => "rocksdb.sim.block.cache.hit",
+            Ticker::SimBlockCacheMiss => "rocksdb.sim.block.cache.miss",
+            Ticker::MemtableHit => "rocksdb.memtable.hit",
+            Ticker::MemtableMiss => "rocksdb.memtable.miss",
+            Ticker::GetHitL0 => "rocksdb.l0.hit",
+            Ticker::GetHitL1 => "rocksdb.l1.hit",
+            Ticker::GetHitL2AndUp => "rocksdb.l2andup.hit",
+            Ticker::CompactionKeyDropNewerEntry => {
+                "rocksdb.compaction.key.drop.new"
+            }
+            Ticker::CompactionKeyDropObsolete => {
+                "rocksdb.compaction.key.drop.obsolete"
+            }
+            Ticker::CompactionKeyDropRangeDel => {
+                "rocksdb.compaction.key.drop.range_del"
+            }
+            Ticker::CompactionKeyDropUser => "rocksdb.compaction.key.drop.user",
+            Ticker::CompactionRangeDelDropObsolete => {
+                "rocksdb.compaction.range_del.drop.obsolete"
+            }
+            Ticker::CompactionOptimizedDelDropObsolete => {
+                "rocksdb.compaction.optimized.del.drop.obsolete"
+            }
+            Ticker::CompactionCancelled => "rocksdb.compaction.cancelled",
+            Ticker::NumberKeysWritten => "rocksdb.number.keys.written",
+            Ticker::NumberKeysRead => "rocksdb.number.keys.read",
+            Ticker::NumberKeysUpdated => "rocksdb.number.keys.updated",
+            Ticker::BytesWritten => "rocksdb.bytes.written",
+            Ticker::BytesRead => "rocksdb.bytes.read",
+            Ticker::NumberDbSeek => "rocksdb.number.db.seek",
+            Ticker::NumberDbNext => "rocksdb.number.db.next",
+            Ticker::NumberDbPrev => "rocksdb.number.db.prev",
+            Ticker::NumberDbSeekFound => "rocksdb.number.db.seek.found",
+            Ticker::NumberDbNextFound => "rocksdb.number.db.next.found",
+            Ticker::NumberDbPrevFound => "rocksdb.number.db.prev.found",
+            Ticker::IterBytesRead => "rocksdb.db.iter.bytes.read",
+            Ticker::NumberIterSkip => "rocksdb.number.iter.skip",
+            Ticker::NumberOfReseeksInIteration => {
+                "rocksdb.number.reseeks.iteration"
+            }
+            Ticker::NoIteratorCreated => "rocksdb.num.iterator.created",
+            Ticker::NoIteratorDeleted => "rocksdb.num.iterator.deleted",
+            Ticker::NoFileOpens => "rocksdb.no.file.opens",
+            Ticker::NoFileErrors => "rocksdb.no.file.errors",
+            Ticker::StallMicros => "rocksdb.stall.micros",
+            Ticker::DbMutexWaitMicros => "rocksdb.db.mutex.wait.micros",
+            Ticker::NumberMultigetCalls => "rocksdb.number.multiget.get",
+            Ticker::NumberMultigetKeysRead => {
+                "rocksdb.number.multiget.keys.read"
+            }
+            Ticker::NumberMultigetBytesRead => {
+                "rocksdb.number.multiget.bytes.read"
+            }
+            Ticker::NumberMultigetKeysFound => {
+                "rocksdb.number.multiget.keys.found"
+            }
+            Ticker::NumberMergeFailures => "rocksdb.number.merge.failures",
+            Ticker::GetUpdatesSinceCalls => "rocksdb.getupdatessince.calls",
+            Ticker::WalFileSynced => "rocksdb.wal.synced",
+            Ticker::WalFileBytes => "rocksdb.wal.bytes",
+            Ticker::WriteDoneBySelf => "rocksdb.write.self",
+            Ticker::WriteDoneByOther => "rocksdb.write.other",
+            Ticker::WriteWithWal => "rocksdb.write.wal",
+            Ticker::CompactReadBytes => "rocksdb.compact.read.bytes",
+            Ticker::CompactWriteBytes => "rocksdb.compact.write.bytes",
+            Ticker::FlushWriteBytes => "rocksdb.flush.write.bytes",
+            Ticker::CompactReadBytesMarked => {
+                "rocksdb.compact.read.marked.bytes"
+            }
+            Ticker::CompactReadBytesPeriodic => {
+                "rocksdb.compact.read.periodic.bytes"
+            }
+            Ticker::CompactReadBytesTtl => "rocksdb.compact.read.ttl.bytes",
+            Ticker::CompactWriteBytesMarked => {
+                "rocksdb.compact.write.marked.bytes"
+            }
+            Ticker::CompactWriteBytesPeriodic => {
+                "rocksdb.compact.write.periodic.bytes"
+            }
+            Ticker::CompactWriteBytesTtl => "rocksdb.compact.write.ttl.bytes",
+            Ticker::NumberDirectLoadTableProperties => {
+                "rocksdb.number.direct.load.table.properties"
+            }
+            Ticker::NumberSuperversionAcquires => {
+                "rocksdb.number.superversion_acquires"
+            }
+            Ticker::NumberSuperversionReleases => {
+                "rocksdb.number.superversion_releases"
+            }
+            Ticker::NumberSuperversionCleanups => {
+                "rocksdb.number.superversion_cleanups"
+            }
+            Ticker::NumberBlockCompressed => "rocksdb.number.block.compressed",
+            Ticker::NumberBlockDecompressed => {
+                "rocksdb.number.block.decompressed"
+            }
+            Ticker::BytesCompressedFrom => "rocksdb.bytes.compressed.from",
+            Ticker::BytesCompressedTo => "rocksdb.bytes.compressed.to",
+            Ticker::BytesCompressionBypassed => {
+                "rocksdb.bytes.compression_bypassed"
+            }
+            Ticker::BytesCompressionRejected => {
+                "rocksdb.bytes.compression.rejected"
+            }
+            Ticker::NumberBlockCompressionBypassed => {
+                "rocksdb.number.block_compression_bypassed"
+            }
+            Ticker::NumberBlockCompressionRejected => {
+                "rocksdb.number.block_compression_rejected"
+            }
+            Ticker::BytesDecompressedFrom => "rocksdb.bytes.decompressed.from",
+            Ticker::BytesDecompressedTo => "rocksdb.bytes.decompressed.to",
+            Ticker::MergeOperationTotalTime => {
+                "rocksdb.merge.operation.time.nanos"
+            }
+            Ticker::FilterOperationTotalTime => {
+                "rocksdb.filter.operation.time.nanos"
+            }
+            Ticker::CompactionCpuTotalTime => {
+                "rocksdb.compaction.total.time.cpu_micros"
+            }
+            Ticker::RowCacheHit => "rocksdb.row.cache.hit",
+            Ticker::RowCacheMiss => "rocksdb.row.cache.miss",
+            Ticker::ReadAmpEstimateUsefulBytes => {
+                "rocksdb.read.amp.estimate.useful.bytes"
+            }
+            Ticker::ReadAmpTotalReadBytes => {
+                "rocksdb.read.amp.total.read.bytes"
+            }
+            Ticker::NumberRateLimiterDrains => {
+                "rocksdb.number.rate_limiter.drains"
+            }
+            Ticker::BlobDbNumPut => "rocksdb.blobdb.num.put",
+            Ticker::BlobDbNumWrite => "rocksdb.blobdb.num.write",
+            Ticker::BlobDbNumGet => "rocksdb.blobdb.num.get",
+            Ticker::BlobDbNumMultiget => "rocksdb.blobdb.num.multiget",
+            Ticker::BlobDbNumSeek => "rocksdb.blobdb.num.seek",
+            Ticker::BlobDbNumNext => "rocksdb.blobdb.num.next",
+            Ticker::BlobDbNumPrev => "rocksdb.blobdb.num.prev",
+            Ticker::BlobDbNumKeysWritten => "rocksdb.blobdb.num.keys.written",
+            Ticker::BlobDbNumKeysRead => "rocksdb.blobdb.num.keys.read",
+            Ticker::BlobDbBytesWritten => "rocksdb.blobdb.bytes.written",
+            Ticker::BlobDbBytesRead => "rocksdb.blobdb.bytes.read",
+            Ticker::BlobDbWriteInlined => "rocksdb.blobdb.write.inlined",
+            Ticker::BlobDbWriteInlinedTtl => "rocksdb.blobdb.write.inlined.ttl",
+            Ticker::BlobDbWriteBlob => "rocksdb.blobdb.write.blob",
+            Ticker::BlobDbWriteBlobTtl => "rocksdb.blobdb.write.blob.ttl",
+            Ticker::BlobDbBlobFileBytesWritten => {
+                "rocksdb.blobdb.blob.file.bytes.written"
+            }
+            Ticker::BlobDbBlobFileBytesRead => {
+                "rocksdb.blobdb.blob.file.bytes.read"
+            }
+            Ticker::BlobDbBlobFileSynced => "rocksdb.blobdb.blob.file.synced",
+            Ticker::BlobDbBlobIndexExpiredCount => {
+                "rocksdb.blobdb.blob.index.expired.count"
+            }
+            Ticker::BlobDbBlobIndexExpiredSize => {
+                "rocksdb.blobdb.blob.index.expired.size"
+            }
+            Ticker::BlobDbBlobIndexEvictedCount => {
+                "rocksdb.blobdb.blob.index.evicted.count"
+            }
+            Ticker::BlobDbBlobIndexEvictedSize => {
+                "rocksdb.blobdb.blob.index.evicted.size"
+            }
+            Ticker::BlobDbGcNumFiles => "rocksdb.blobdb.gc.num.files",
+            Ticker::BlobDbGcNumNewFiles => "rocksdb.blobdb.gc.num.new.files",
+            Ticker::BlobDbGcFailures => "rocksdb.blobdb.gc.failures",
+            Ticker::BlobDbGcNumKeysRelocated => {
+                "rocksdb.blobdb.gc.num.keys.relocated"
+            }
+            Ticker::BlobDbGcBytesRelocated => {
+                "rocksdb.blobdb.gc.bytes.relocated"
+            }
+            Ticker::BlobDbFifoNumFilesEvicted => {
+                "rocksdb.blobdb.fifo.num.files.evicted"
+            }
+            Ticker::BlobDbFifoNumKeysEvicted => {
+                "rocksdb.blobdb.fifo.num.keys.evicted"
+            }
+            Ticker::BlobDbFifoBytesEvicted => {
+                "rocksdb.blobdb.fifo.bytes.evicted"
+            }
+            Ticker::BlobDbCacheMiss => "rocksdb.blobdb.cache.miss",
+            Ticker::BlobDbCacheHit => "rocksdb.blobdb.cache.hit",
+            Ticker::BlobDbCacheAdd => "rocksdb.blobdb.cache.add",
+            Ticker::BlobDbCacheAddFailures => {
+                "rocksdb.blobdb.cache.add.failures"
+            }
+            Ticker::BlobDbCacheBytesRead => "rocksdb.blobdb.cache.bytes.read",
+            Ticker::BlobDbCacheBytesWrite => "rocksdb.blobdb.cache.bytes.write",
+            Ticker::TxnPrepareMutexOverhead => {
+                "rocksdb.txn.overhead.mutex.prepare"
+            }
+            Ticker::TxnOldCommitMapMutexOverhead => {
+                "rocksdb.txn.overhead.mutex.old.commit.map"
+            }
+            Ticker::TxnDuplicateKeyOverhead => {
+                "rocksdb.txn.overhead.duplicate.key"
+            }
+            Ticker::TxnSnapshotMutexOverhead => {
+                "rocksdb.txn.overhead.mutex.snapshot"
+            }
+            Ticker::TxnGetTryAgain => "rocksdb.txn.get.tryagain",
+            Ticker::FilesMarkedTrash => "rocksdb.files.marked.trash",
+            Ticker::FilesDeletedFromTrashQueue => {
+                "rocksdb.files.marked.trash.deleted"
+            }
+            Ticker::FilesDeletedImmediately => {
+                "rocksdb.files.deleted.immediately"
+            }
+            Ticker::ErrorHandlerBgErrorCount => {
+                "rocksdb.error.handler.bg.error.count"
+            }
+            Ticker::ErrorHandlerBgIoErrorCount => {
+                "rocksdb.error.handler.bg.io.error.count"
+            }
+            Ticker::ErrorHandlerBgRetryableIoErrorCount => {
+                "rocksdb.error.handler.bg.retryable.io.error.count"
+            }
+            Ticker::ErrorHandlerAutoresumeCount => {
+                "rocksdb.error.handler.autoresume.count"
+            }
+            Ticker::ErrorHandlerAutoresumeRetryTotalCount => {
+                "rocksdb.error.handler.autoresume.retry.total.count"
+            }
+            Ticker::ErrorHandlerAutoresumeSuccessCount => {
+                "rocksdb.error.handler.autoresume.success.count"
+            }
+            Ticker::MemtablePayloadBytesAtFlush => {
+                "rocksdb.memtable.payload.bytes.at.flush"
+            }
+            Ticker::MemtableGarbageBytesAtFlush => {
+                "rocksdb.memtable.garbage.bytes.at.flush"
+            }
+            Ticker::VerifyChecksumReadBytes => {
+                "rocksdb.verify_checksum.read.bytes"
+            }
+            Ticker::BackupReadBytes => "rocksdb.backup.read.bytes",
+            Ticker::BackupWriteBytes => "rocksdb.backup.write.bytes",
+            Ticker::RemoteCompactReadBytes => {
+                "rocksdb.remote.compact.read.bytes"
+            }
+            Ticker::RemoteCompactWriteBytes => {
+                "rocksdb.remote.compact.write.bytes"
+            }
+            Ticker::HotFileReadBytes => "rocksdb.hot.file.read.bytes",
+            Ticker::WarmFileReadBytes => "rocksdb.warm.file.read.bytes",
+            Ticker::ColdFileReadBytes => "rocksdb.cold.file.read.bytes",
+            Ticker::HotFileReadCount => "rocksdb.hot.file.read.count",
+            Ticker::WarmFileReadCount => "rocksdb.warm.file.read.count",
+            Ticker::ColdFileReadCount => "rocksdb.cold.file.read.count",
+            Ticker::LastLevelReadBytes => "rocksdb.last.level.read.bytes",
+            Ticker::LastLevelReadCount => "rocksdb.last.level.read.count",
+            Ticker::NonLastLevelReadBytes => {
+                "rocksdb.non.last.level.read.bytes"
+            }
+            Ticker::NonLastLevelReadCount => {
+                "rocksdb.non.last.level.read.count"
+            }
+            Ticker::LastLevelSeekFiltered => "rocksdb.last.level.seek.filtered",
+            Ticker::LastLevelSeekFilterMatch => {
+                "rocksdb.last.level.seek.filter.match"
+            }
+            Ticker::LastLevelSeekData => "rocksdb.last.level.seek.data",
+            Ticker::LastLevelSeekDataUsefulNoFilter => {
+                "rocksdb.last.level.seek.data.useful.no.filter"
+            }
+            Ticker::LastLevelSeekDataUsefulFilterMatch => {
+                "rocksdb.last.level.seek.data.useful.filter.match"
+            }
+            Ticker::NonLastLevelSeekFiltered => {
+                "rocksdb.non.last.level.seek.filtered"
+            }
+            Ticker::NonLastLevelSeekFilterMatch => {
+                "rocksdb.non.last.level.seek.filter.match"
+            }
+            Ticker::NonLastLevelSeekData => "rocksdb.non.last.level.seek.data",
+            Ticker::NonLastLevelSeekDataUsefulNoFilter => {
+                "rocksdb.non.last.level.seek.data.useful.no.filter"
+            }
+            Ticker::NonLastLevelSeekDataUsefulFilterMatch => {
+                "rocksdb.non.last.level.seek.data.useful.filter.match"
+            }
+            Ticker::BlockChecksumComputeCount => {
+                "rocksdb.block.checksum.compute.count"
+            }
+            Ticker::BlockChecksumMismatchCount => {
+                "rocksdb.block.checksum.mismatch.count"
+            }
+            Ticker::MultigetCoroutineCount => {
+                "rocksdb.multiget.coroutine.count"
+            }
+            Ticker::ReadAsyncMicros => "rocksdb.read.async.micros",
+            Ticker::AsyncReadErrorCount => "rocksdb.async.read.error.count",
+            Ticker::TableOpenPrefetchTailMiss => {
+                "rocksdb.table.open.prefetch.tail.miss"
+            }
+            Ticker::TableOpenPrefetchTailHit => {
+                "rocksdb.table.open.prefetch.tail.hit"
+            }
+            Ticker::TimestampFilterTableChecked => {
+                "rocksdb.timestamp.filter.table.checked"
+            }
+            Ticker::TimestampFilterTableFiltered => {
+                "rocksdb.timestamp.filter.table.filtered"
+            }
+            Ticker::ReadaheadTrimmed => "rocksdb.readahead.trimmed",
+            Ticker::FifoMaxSizeCompactions => {
+                "rocksdb.fifo.max.size.compactions"
+            }
+            Ticker::FifoTtlCompactions => "rocksdb.fifo.ttl.compactions",
+            Ticker::FifoChangeTemperatureCompactions => {
+                "rocksdb.fifo.change_temperature.compactions"
+            }
+            Ticker::PrefetchBytes => "rocksdb.prefetch.bytes",
+            Ticker::PrefetchBytesUseful => "rocksdb.prefetch.bytes.useful",
+            Ticker::PrefetchHits => "rocksdb.prefetch.hits",
+            Ticker::SstFooterCorruptionCount => {
+                "rocksdb.footer.corruption.count"
+            }
+            Ticker::FileReadCorruptionRetryCount => {
+                "rocksdb.file.read.corruption.retry.count"
+            }
+            Ticker::FileReadCorruptionRetrySuccessCount => {
+                "rocksdb.file.read.corruption.retry.success.count"
+            }
+            Ticker::NumberWbwiIngest => "rocksdb.number.wbwi.ingest",
+        }
+    }
+}
+
+// Mirrors `enum Histograms` in rocksdb/statistics.h, with the same caveat
+// about ordering as `Ticker`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum Histogram {
+    DbGet,
+    DbWrite,
+    CompactionTime,
+    CompactionCpuTime,
+    SubcompactionSetupTime,
+    TableSyncMicros,
+    CompactionOutfileSyncMicros,
+    WalFileSyncMicros,
+    ManifestFileSyncMicros,
+    TableOpenIoMicros,
+    DbMultiget,
+    ReadBlockCompactionMicros,
+    ReadBlockGetMicros,
+    WriteRawBlockMicros,
+    NumFilesInSingleCompaction,
+    DbSeek,
+    WriteStall,
+    SstReadMicros,
+    FileReadFlushMicros,
+    FileReadCompactionMicros,
+    FileReadDbOpenMicros,
+    FileReadGetMicros,
+    FileReadMultigetMicros,
+    FileReadDbIteratorMicros,
+    FileReadVerifyDbChecksumMicros,
+    FileReadVerifyFileChecksumsMicros,
+    SstWriteMicros,
+    FileWriteFlushMicros,
+    FileWriteCompactionMicros,
+    FileWriteDbOpenMicros,
+    NumSubcompactionsScheduled,
+    BytesPerRead,
+    BytesPerWrite,
+    BytesPerMultiget,
+    CompressionTimesNanos,
+    DecompressionTimesNanos,
+    ReadNumMergeOperands,
+    BlobDbKeySize,
+    BlobDbValueSize,
+    BlobDbWriteMicros,
+    BlobDbGetMicros,
+    BlobDbMultigetMicros,
+    BlobDbSeekMicros,
+    BlobDbNextMicros,
+    BlobDbPrevMicros,
+    BlobDbBlobFileWriteMicros,
+    BlobDbBlobFileReadMicros,
+    BlobDbBlobFileSyncMicros,
+    BlobDbCompressionMicros,
+    BlobDbDecompressionMicros,
+    FlushTime,
+    SstBatchSize,
+    MultigetIoBatchSize,
+    NumIndexAndFilterBlocksReadPerLevel,
+    NumSstReadPerLevel,
+    NumLevelReadPerMultiget,
+    ErrorHandlerAutoresumeRetryCount,
+    AsyncReadBytes,
+    PollWaitMicros,
+    CompactionPrefetchBytes,
+    PrefetchedBytesDiscarded,
+    AsyncPrefetchAbortMicros,
+    TableOpenPrefetchTailReadBytes,
+    NumOpPerTransaction,
+}
+
+impl Histogram {
+    /// The name RocksDB uses for this histogram in its statistics dump.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Histogram::DbGet => "rocksdb.db.get.micros",
+            Histogram::DbWrite => "rocksdb.db.write.micros",
+            Histogram::CompactionTime => "rocksdb.compaction.times.micros",
+            Histogram::CompactionCpuTime => {
+                "rocksdb.compaction.times.cpu_micros"
+            }
+            Histogram::SubcompactionSetupTime => {
+                "rocksdb.subcompaction.setup.times.micros"
+            }
+            Histogram::TableSyncMicros => "rocksdb.table.sync.micros",
+            Histogram::CompactionOutfileSyncMicros => {
+                "rocksdb.compaction.outfile.sync.micros"
+            }
+            Histogram::WalFileSyncMicros => "rocksdb.wal.file.sync.micros",
+            Histogram::ManifestFileSyncMicros => {
+                "rocksdb.manifest.file.sync.micros"
+            }
+            Histogram::TableOpenIoMicros => "rocksdb.table.open.io.micros",
+            Histogram::DbMultiget => "rocksdb.db.multiget.micros",
+            Histogram::ReadBlockCompactionMicros => {
+                "rocksdb.read.block.compaction.micros"
+            }
+            Histogram::ReadBlockGetMicros => "rocksdb.read.block.get.micros",
+            Histogram::WriteRawBlockMicros => "rocksdb.write.raw.block.micros",
+            Histogram::NumFilesInSingleCompaction => {
+                "rocksdb.numfiles.in.singlecompaction"
+            }
+            Histogram::DbSeek => "rocksdb.db.seek.micros",
+            Histogram::WriteStall => "rocksdb.db.write.stall",
+            Histogram::SstReadMicros => "rocksdb.sst.read.micros",
+            Histogram::FileReadFlushMicros => "rocksdb.file.read.flush.micros",
+            Histogram::FileReadCompactionMicros => {
+                "rocksdb.file.read.compaction.micros"
+            }
+            Histogram::FileReadDbOpenMicros => {
+                "rocksdb.file.read.db.open.micros"
+            }
+            Histogram::FileReadGetMicros => "rocksdb.file.read.get.micros",
+            Histogram::FileReadMultigetMicros => {
+                "rocksdb.file.read.multiget.micros"
+            }
+            Histogram::FileReadDbIteratorMicros => {
+                "rocksdb.file.read.db.iterator.micros"
+            }
+            Histogram::FileReadVerifyDbChecksumMicros => {
+                "rocksdb.file.read.verify.db.checksum.micros"
+            }
+            Histogram::FileReadVerifyFileChecksumsMicros => {
+                "rocksdb.file.read.verify.file.checksums.micros"
+            }
+            Histogram::SstWriteMicros => "rocksdb.sst.write.micros",
+            Histogram::FileWriteFlushMicros => {
+                "rocksdb.file.write.flush.micros"
+            }
+            Histogram::FileWriteCompactionMicros => {
+                "rocksdb.file.write.compaction.micros"
+            }
+            Histogram::FileWriteDbOpenMicros => {
+                "rocksdb.file.write.db.open.micros"
+            }
+            Histogram::NumSubcompactionsScheduled => {
+                "rocksdb.num.subcompactions.scheduled"
+            }
+            Histogram::BytesPerRead => "rocksdb.bytes.per.read",
+            Histogram::BytesPerWrite => "rocksdb.bytes.per.write",
+            Histogram::BytesPerMultiget => "rocksdb.bytes.per.multiget",
+            Histogram::CompressionTimesNanos => {
+                "rocksdb.compression.times.nanos"
+            }
+            Histogram::DecompressionTimesNanos => {
+                "rocksdb.decompression.times.nanos"
+            }
+            Histogram::ReadNumMergeOperands => {
+                "rocksdb.read.num.merge_operands"
+            }
+            Histogram::BlobDbKeySize => "rocksdb.blobdb.key.size",
+            Histogram::BlobDbValueSize => "rocksdb.blobdb.value.size",
+            Histogram::BlobDbWriteMicros => "rocksdb.blobdb.write.micros",
+            Histogram::BlobDbGetMicros => "rocksdb.blobdb.get.micros",
+            Histogram::BlobDbMultigetMicros => "rocksdb.blobdb.multiget.micros",
+            Histogram::BlobDbSeekMicros => "rocksdb.blobdb.seek.micros",
+            Histogram::BlobDbNextMicros => "rocksdb.blobdb.next.micros",
+            Histogram::BlobDbPrevMicros => "rocksdb.blobdb.prev.micros",
+            Histogram::BlobDbBlobFileWriteMicros => {
+                "rocksdb.blobdb.blob.file.write.micros"
+            }
+            Histogram::BlobDbBlobFileReadMicros => {
+                "rocksdb.blobdb.blob.file.read.micros"
+            }
+            Histogram::BlobDbBlobFileSyncMicros => {
+                "rocksdb.blobdb.blob.file.sync.micros"
+            }
+            Histogram::BlobDbCompressionMicros => {
+                "rocksdb.blobdb.compression.micros"
+            }
+            Histogram::BlobDbDecompressionMicros => {
+                "rocksdb.blobdb.decompression.micros"
+            }
+            Histogram::FlushTime => "rocksdb.db.flush.micros",
+            Histogram::SstBatchSize => "rocksdb.sst.batch.size",
+            Histogram::MultigetIoBatchSize => "rocksdb.multiget.io.batch.size",
+            Histogram::NumIndexAndFilterBlocksReadPerLevel => {
+                "rocksdb.num.index.and.filter.blocks.read.per.level"
+            }
+            Histogram::NumSstReadPerLevel => "rocksdb.num.sst.read.per.level",
+            Histogram::NumLevelReadPerMultiget => {
+                "rocksdb.num.level.read.per.multiget"
+            }
+            Histogram::ErrorHandlerAutoresumeRetryCount => {
+                "rocksdb.error.handler.autoresume.retry.count"
+            }
+            Histogram::AsyncReadBytes => "rocksdb.async.read.bytes",
+            Histogram::PollWaitMicros => "rocksdb.poll.wait.micros",
+            Histogram::CompactionPrefetchBytes => {
+                "rocksdb.compaction.prefetch.bytes"
+            }
+            Histogram::PrefetchedBytesDiscarded => {
+                "rocksdb.prefetched.bytes.discarded"
+            }
+            Histogram::AsyncPrefetchAbortMicros => {
+                "rocksdb.async.prefetch.abort.micros"
+            }
+            Histogram::TableOpenPrefetchTailReadBytes => {
+                "rocksdb.table.open.prefetch.tail.read.bytes"
+            }
+            Histogram::NumOpPerTransaction => "rocksdb.num.op.per.transaction",
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate libc;
+use self::libc::{c_char, c_void, size_t};
+use std::marker::PhantomData;
+
+use rocksdb::{DBVector, Writable};
+use rocksdb_ffi::{self, DBCFHandle, DBReadOptions, error_message};
+
+/// A group of reads and writes that commit atomically. Writes stay private
+/// to the transaction until `commit`, while its own reads see them.
+/// Dropping a transaction without committing it discards its writes.
+pub struct Transaction<'a> {
+    inner: rocksdb_ffi::DBTransaction,
+    db: PhantomData<&'a ()>,
+}
+
+impl<'a> Transaction<'a> {
+    /// Wraps a transaction handed out by RocksDB; the transaction must not
+    /// outlive the database that began it.
+    pub unsafe fn from_raw(inner: rocksdb_ffi::DBTransaction)
+                           -> Transaction<'a> {
+        Transaction {
+            inner: inner,
+            db: PhantomData,
+        }
+    }
+
+    /// Reads `key`, seeing this transaction's own writes. If the
+    /// transaction was begun with a snapshot, reads are as of that
+    /// snapshot.
+    pub fn get(&self, key: &[u8]) -> Result<Option<DBVector>, String> {
+        self.read(|readopts, val_len, err| unsafe {
+            rocksdb_ffi::rocksdb_transaction_get(self.inner,
+                                                 readopts,
+                                                 key.as_ptr(),
+                                                 key.len() as size_t,
+                                                 val_len,
+                                                 err)
+        })
+    }
+
+    pub fn get_cf(&self,
+                  cf: DBCFHandle,
+                  key: &[u8])
+                  -> Result<Option<DBVector>, String> {
+        self.read(|readopts, val_len, err| unsafe {
+            rocksdb_ffi::rocksdb_transaction_get_cf(self.inner,
+                                                    readopts,
+                                                    cf,
+                                                    key.as_ptr(),
+                                                    key.len() as size_t,
+                                                    val_len,
+                                                    err)
+        })
+    }
+
+    /// Like `get`, but also guards `key` against other writers until the
+    /// transaction ends. In a `TransactionDB` this takes a lock on `key`,
+    /// shared unless `exclusive` is set, and fails with "Operation timed
+    /// out" if the lock can't be had in time. In an
+    /// `OptimisticTransactionDB` it makes `commit` fail if someone else
+    /// writes `key` first.
+    pub fn get_for_update(&self,
+                          key: &[u8],
+                          exclusive: bool)
+                          -> Result<Option<DBVector>, String> {
+        self.read(|readopts, val_len, err| unsafe {
+            rocksdb_ffi::rocksdb_transaction_get_for_update(self.inner,
+                                                            readopts,
+                                                            key.as_ptr(),
+                                                            key.len() as size_t,
+                                                            val_len,
+                                                            exclusive as u8,
+                                                            err)
+        })
+    }
+
+    pub fn get_for_update_cf(&self,
+                             cf: DBCFHandle,
+                             key: &[u8],
+                             exclusive: bool)
+                             -> Result<Option<DBVector>, String> {
+        self.read(|readopts, val_len, err| unsafe {
+            rocksdb_ffi::rocksdb_transaction_get_for_update_cf(
+                self.inner,
+                readopts,
+                cf,
+                key.as_ptr(),
+                key.len() as size_t,
+                val_len,
+                exclusive as u8,
+                err)
+        })
+    }
+
+    /// Applies the transaction's writes. In an `OptimisticTransactionDB`,
+    /// if another writer touched a key this transaction read with
+    /// `get_for_update` (or wrote) since it was read, nothing is written
+    /// and the error starts with "Resource busy".
+    pub fn commit(self) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_commit(self.inner, err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    /// Discards all of the transaction's writes and releases its locks.
+    pub fn rollback(self) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_rollback(self.inner, err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    /// Records a point that `rollback_to_savepoint` can return to.
+    /// Savepoints nest.
+    pub fn set_savepoint(&self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_set_savepoint(self.inner);
+        }
+    }
+
+    /// Undoes the writes made since the most recent savepoint, and forgets
+    /// that savepoint. Fails if there is no savepoint to return to.
+    pub fn rollback_to_savepoint(&self) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_rollback_to_savepoint(self.inner,
+                                                                   err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    /// Names the transaction, which `prepare` requires. Names must be
+    /// unique among the transactions of a `TransactionDB`.
+    pub fn set_name(&self, name: &str) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_set_name(self.inner,
+                                                      name.as_ptr() as
+                                                      *const c_char,
+                                                      name.len() as size_t,
+                                                      err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    /// The first phase of a two-phase commit: makes the transaction's
+    /// writes durable in the WAL without applying them. Only supported by
+    /// `TransactionDB`.
+    pub fn prepare(&self) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_prepare(self.inner, err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    fn read<F>(&self, f: F) -> Result<Option<DBVector>, String>
+        where F: FnOnce(DBReadOptions, *const size_t, *mut *const i8)
+                        -> *mut c_void
+    {
+        unsafe {
+            let readopts = rocksdb_ffi::rocksdb_readoptions_create();
+            // Points the reads at the transaction's snapshot, if it has one.
+            // The read options copy the snapshot out of RocksDB's wrapper,
+            // which is malloc'd for us to free.
+            let snapshot =
+                rocksdb_ffi::rocksdb_transaction_get_snapshot(self.inner);
+            rocksdb_ffi::rocksdb_readoptions_set_snapshot(readopts, snapshot);
+            libc::free(snapshot.0 as *mut c_void);
+
+            let val_len: size_t = 0;
+            let val_len_ptr = &val_len as *const size_t;
+            let mut err: *const i8 = 0 as *const i8;
+            let err_ptr: *mut *const i8 = &mut err;
+            let val = f(readopts, val_len_ptr, err_ptr) as *mut u8;
+            rocksdb_ffi::rocksdb_readoptions_destroy(readopts);
+            if !err.is_null() {
+                return Err(error_message(err));
+            }
+            match val.is_null() {
+                true => Ok(None),
+                false => Ok(Some(DBVector::from_c(val, val_len))),
+            }
+        }
+    }
+}
+
+impl<'a> Writable for Transaction<'a> {
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_put(self.inner,
+                                                 key.as_ptr(),
+                                                 key.len() as size_t,
+                                                 value.as_ptr(),
+                                                 value.len() as size_t,
+                                                 err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    fn put_cf(&self,
+              cf: DBCFHandle,
+              key: &[u8],
+              value: &[u8])
+              -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_put_cf(self.inner,
+                                                    cf,
+                                                    key.as_ptr(),
+                                                    key.len() as size_t,
+                                                    value.as_ptr(),
+                                                    value.len() as size_t,
+                                                    err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    fn merge(&self, key: &[u8], value: &[u8]) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_merge(self.inner,
+                                                   key.as_ptr(),
+                                                   key.len() as size_t,
+                                                   value.as_ptr(),
+                                                   value.len() as size_t,
+                                                   err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    fn merge_cf(&self,
+                cf: DBCFHandle,
+                key: &[u8],
+                value: &[u8])
+                -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_merge_cf(self.inner,
+                                                      cf,
+                                                      key.as_ptr(),
+                                                      key.len() as size_t,
+                                                      value.as_ptr(),
+                                                      value.len() as size_t,
+                                                      err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_delete(self.inner,
+                                                    key.as_ptr(),
+                                                    key.len() as size_t,
+                                                    err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+
+    fn delete_cf(&self, cf: DBCFHandle, key: &[u8]) -> Result<(), String> {
+        let mut err: *const i8 = 0 as *const i8;
+        let err_ptr: *mut *const i8 = &mut err;
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_delete_cf(self.inner,
+                                                       cf,
+                                                       key.as_ptr(),
+                                                       key.len() as size_t,
+                                                       err_ptr);
+        }
+        if !err.is_null() {
+            return Err(error_message(err));
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Drop for Transaction<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_destroy(self.inner);
+        }
+    }
+}
@@ -0,0 +1,312 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate libc;
+use self::libc::size_t;
+use std::collections::BTreeMap;
+use std::ffi::CString;
+
+use rocksdb::{ColumnFamilyDescriptor, DBVector, create_dirs,
+              open_cf_descriptors_with};
+use rocksdb_ffi::{self, DBCFHandle, error_message};
+use rocksdb_options::Options;
+use transaction::Transaction;
+
+/// A database whose transactions lock the keys they write or read with
+/// `get_for_update`, so conflicting transactions wait for each other
+/// instead of failing at commit. Suited to hot keys, where optimistic
+/// transactions would keep retrying.
+pub struct TransactionDB {
+    inner: rocksdb_ffi::DBTransactionDB,
+    cfs: BTreeMap<String, DBCFHandle>,
+}
+
+unsafe impl Send for TransactionDB {}
+unsafe impl Sync for TransactionDB {}
+
+pub struct TransactionDBOptions {
+    inner: rocksdb_ffi::DBTransactionDBOptions,
+}
+
+impl TransactionDBOptions {
+    pub fn new() -> TransactionDBOptions {
+        let opts = unsafe {
+            rocksdb_ffi::rocksdb_transactiondb_options_create()
+        };
+        TransactionDBOptions { inner: opts }
+    }
+
+    /// Caps the number of keys locked at once in each column family;
+    /// taking more locks then fails. Zero or less means no limit.
+    pub fn set_max_num_locks(&mut self, max_num_locks: i64) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transactiondb_options_set_max_num_locks(
+                self.inner,
+                max_num_locks);
+        }
+    }
+
+    /// Splits each column family's lock table into this many independently
+    /// locked stripes, to reduce contention.
+    pub fn set_num_stripes(&mut self, num_stripes: usize) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transactiondb_options_set_num_stripes(
+                self.inner,
+                num_stripes as size_t);
+        }
+    }
+
+    /// Default for how many milliseconds a transaction waits for a lock,
+    /// unless its `TransactionOptions` say otherwise. A negative value
+    /// waits forever.
+    pub fn set_transaction_lock_timeout(&mut self, millis: i64) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transactiondb_options_set_transaction_lock_timeout(
+                self.inner,
+                millis);
+        }
+    }
+
+    /// How many milliseconds writes made directly to the database, outside
+    /// a transaction, wait for a lock. A negative value waits forever.
+    pub fn set_default_lock_timeout(&mut self, millis: i64) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transactiondb_options_set_default_lock_timeout(
+                self.inner,
+                millis);
+        }
+    }
+}
+
+impl Drop for TransactionDBOptions {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transactiondb_options_destroy(self.inner);
+        }
+    }
+}
+
+pub struct TransactionOptions {
+    inner: rocksdb_ffi::DBTransactionOptions,
+}
+
+impl TransactionOptions {
+    pub fn new() -> TransactionOptions {
+        let opts = unsafe { rocksdb_ffi::rocksdb_transaction_options_create() };
+        TransactionOptions { inner: opts }
+    }
+
+    /// Takes a snapshot when the transaction begins; its reads then see the
+    /// database as of that snapshot.
+    pub fn set_snapshot(&mut self, snapshot: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_options_set_set_snapshot(
+                self.inner,
+                snapshot as u8);
+        }
+    }
+
+    /// Checks for a deadlock whenever the transaction has to wait for a
+    /// lock. A lock request that would deadlock fails with "Resource busy"
+    /// instead of waiting for the lock timeout.
+    pub fn set_deadlock_detect(&mut self, deadlock_detect: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_options_set_deadlock_detect(
+                self.inner,
+                deadlock_detect as u8);
+        }
+    }
+
+    /// How many waiting transactions deadlock detection follows before
+    /// giving up.
+    pub fn set_deadlock_detect_depth(&mut self, depth: i64) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_options_set_deadlock_detect_depth(
+                self.inner,
+                depth);
+        }
+    }
+
+    /// How many milliseconds this transaction waits for a lock, overriding
+    /// `TransactionDBOptions::set_transaction_lock_timeout`.
+    pub fn set_lock_timeout(&mut self, millis: i64) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_options_set_lock_timeout(
+                self.inner,
+                millis);
+        }
+    }
+
+    /// Lets other transactions steal this one's locks once it has been
+    /// running for this many milliseconds; it can then no longer commit.
+    /// A negative value never expires it.
+    pub fn set_expiration(&mut self, millis: i64) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_options_set_expiration(self.inner,
+                                                                   millis);
+        }
+    }
+}
+
+impl Drop for TransactionOptions {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_transaction_options_destroy(self.inner);
+        }
+    }
+}
+
+impl TransactionDB {
+    pub fn open(opts: &Options,
+                txn_db_opts: &TransactionDBOptions,
+                path: &str)
+                -> Result<TransactionDB, String> {
+        TransactionDB::open_cf(opts, txn_db_opts, path, &[])
+    }
+
+    /// Opens the column families `cfs`, plus "default", all configured
+    /// with `opts`.
+    pub fn open_cf(opts: &Options,
+                   txn_db_opts: &TransactionDBOptions,
+                   path: &str,
+                   cfs: &[&str])
+                   -> Result<TransactionDB, String> {
+        let descriptors = cfs.iter()
+                             .map(|cf| {
+                                 ColumnFamilyDescriptor::new(cf, opts.clone())
+                             })
+                             .collect();
+        TransactionDB::open_cf_descriptors(opts, txn_db_opts, path, descriptors)
+    }
+
+    /// Opens each column family with the options in its descriptor. If
+    /// `cfs` leaves out "default", it is opened with `opts`.
+    pub fn open_cf_descriptors(opts: &Options,
+                               txn_db_opts: &TransactionDBOptions,
+                               path: &str,
+                               cfs: Vec<ColumnFamilyDescriptor>)
+                               -> Result<TransactionDB, String> {
+        let cpath = match CString::new(path.as_bytes()) {
+            Ok(c) => c,
+            Err(_) => return Err("Failed to convert path to CString when \
+                                  opening rocksdb"
+                                     .to_string()),
+        };
+        create_dirs(opts, path, &cfs)?;
+        let open = |num_cfs, names, cf_opts, handles, err| unsafe {
+            rocksdb_ffi::rocksdb_transactiondb_open_column_families(
+                opts.inner,
+                txn_db_opts.inner,
+                cpath.as_ptr(),
+                num_cfs,
+                names,
+                cf_opts,
+                handles,
+                err)
+        };
+        let (db, cf_map) = open_cf_descriptors_with(opts, cfs, open)?;
+        if db.0.is_null() {
+            return Err("Could not initialize database.".to_string());
+        }
+        Ok(TransactionDB {
+            inner: db,
+            cfs: cf_map,
+        })
+    }
+
+    pub fn cf_handle(&self, name: &str) -> Option<&DBCFHandle> {
+        self.cfs.get(name)
+    }
+
+    pub fn transaction(&self) -> Transaction {
+        self.transaction_opt(&TransactionOptions::new())
+    }
+
+    pub fn transaction_opt(&self, txn_opts: &TransactionOptions) -> Transaction {
+        unsafe {
+            let writeopts = rocksdb_ffi::rocksdb_writeoptions_create();
+            let txn = rocksdb_ffi::rocksdb_transaction_begin(
+                self.inner,
+                writeopts,
+                txn_opts.inner,
+                rocksdb_ffi::DBTransaction(0 as *const _));
+            rocksdb_ffi::rocksdb_writeoptions_destroy(writeopts);
+            Transaction::from_raw(txn)
+        }
+    }
+
+    /// Reads the latest committed value of `key`, outside any transaction.
+    pub fn get(&self, key: &[u8]) -> Result<Option<DBVector>, String> {
+        self.get_opt(None, key)
+    }
+
+    pub fn get_cf(&self,
+                  cf: DBCFHandle,
+                  key: &[u8])
+                  -> Result<Option<DBVector>, String> {
+        self.get_opt(Some(cf), key)
+    }
+
+    fn get_opt(&self,
+               cf: Option<DBCFHandle>,
+               key: &[u8])
+               -> Result<Option<DBVector>, String> {
+        unsafe {
+            let readopts = rocksdb_ffi::rocksdb_readoptions_create();
+            let val_len: size_t = 0;
+            let val_len_ptr = &val_len as *const size_t;
+            let mut err: *const i8 = 0 as *const i8;
+            let err_ptr: *mut *const i8 = &mut err;
+            let val = match cf {
+                Some(cf) => {
+                    rocksdb_ffi::rocksdb_transactiondb_get_cf(self.inner,
+                                                             readopts,
+                                                             cf,
+                                                             key.as_ptr(),
+                                                             key.len() as
+                                                             size_t,
+                                                             val_len_ptr,
+                                                             err_ptr)
+                }
+                None => rocksdb_ffi::rocksdb_transactiondb_get(self.inner,
+                                                               readopts,
+                                                               key.as_ptr(),
+                                                               key.len() as
+                                                               size_t,
+                                                               val_len_ptr,
+                                                               err_ptr),
+            } as *mut u8;
+            rocksdb_ffi::rocksdb_readoptions_destroy(readopts);
+            if !err.is_null() {
+                return Err(error_message(err));
+            }
+            match val.is_null() {
+                true => Ok(None),
+                false => Ok(Some(DBVector::from_c(val, val_len))),
+            }
+        }
+    }
+}
+
+impl Drop for TransactionDB {
+    fn drop(&mut self) {
+        unsafe {
+            for (_, cf) in self.cfs.iter() {
+                rocksdb_ffi::rocksdb_column_family_handle_destroy(*cf);
+            }
+            rocksdb_ffi::rocksdb_transactiondb_close(self.inner);
+        }
+    }
+}
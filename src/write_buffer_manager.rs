@@ -0,0 +1,140 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate libc;
+use self::libc::size_t;
+use std::sync::Arc;
+
+use rocksdb_ffi;
+use cache::Cache;
+
+/// Bounds the memory used by memtables across every column family and
+/// database whose `Options` it is set on. Clones refer to the same
+/// underlying manager.
+#[derive(Clone)]
+pub struct WriteBufferManager {
+    inner: Arc<WriteBufferManagerHandle>,
+}
+
+struct WriteBufferManagerHandle(rocksdb_ffi::DBWriteBufferManager);
+
+unsafe impl Send for WriteBufferManagerHandle {}
+unsafe impl Sync for WriteBufferManagerHandle {}
+
+impl Drop for WriteBufferManagerHandle {
+    fn drop(&mut self) {
+        unsafe {
+            rocksdb_ffi::rocksdb_write_buffer_manager_destroy(self.0);
+        }
+    }
+}
+
+impl WriteBufferManager {
+    /// Flushes memtables once together they take up more than
+    /// `buffer_size` bytes. With `allow_stall`, writes also stall until
+    /// memory usage drops back under the limit. A `buffer_size` of 0
+    /// disables the limit.
+    pub fn new(buffer_size: usize, allow_stall: bool) -> WriteBufferManager {
+        WriteBufferManager::from_raw(unsafe {
+            rocksdb_ffi::rocksdb_write_buffer_manager_create(
+                buffer_size as size_t,
+                allow_stall)
+        })
+    }
+
+    /// Like `new`, but also charges memtable memory to `cache`, so block
+    /// cache and memtables are held to a single budget.
+    pub fn new_with_cache(buffer_size: usize,
+                          cache: &Cache,
+                          allow_stall: bool)
+                          -> WriteBufferManager {
+        WriteBufferManager::from_raw(unsafe {
+            rocksdb_ffi::rocksdb_write_buffer_manager_create_with_cache(
+                buffer_size as size_t,
+                cache.inner(),
+                allow_stall)
+        })
+    }
+
+    fn from_raw(wbm: rocksdb_ffi::DBWriteBufferManager) -> WriteBufferManager {
+        if wbm.0.is_null() {
+            panic!("Could not create rocksdb write buffer manager");
+        }
+        WriteBufferManager { inner: Arc::new(WriteBufferManagerHandle(wbm)) }
+    }
+
+    /// Whether a limit is set.
+    pub fn enabled(&self) -> bool {
+        unsafe { rocksdb_ffi::rocksdb_write_buffer_manager_enabled(self.inner.0) }
+    }
+
+    /// Whether memtable memory is charged to a cache.
+    pub fn cost_to_cache(&self) -> bool {
+        unsafe {
+            rocksdb_ffi::rocksdb_write_buffer_manager_cost_to_cache(self.inner.0)
+        }
+    }
+
+    /// The number of bytes used by all memtables.
+    pub fn memory_usage(&self) -> usize {
+        unsafe {
+            rocksdb_ffi::rocksdb_write_buffer_manager_memory_usage(
+                self.inner.0) as usize
+        }
+    }
+
+    /// The number of bytes used by memtables that are still taking writes.
+    pub fn mutable_memtable_memory_usage(&self) -> usize {
+        unsafe {
+            rocksdb_ffi::rocksdb_write_buffer_manager_mutable_memtable_memory_usage(
+                self.inner.0) as usize
+        }
+    }
+
+    /// The number of bytes reserved in the cache on behalf of memtables.
+    pub fn dummy_entries_in_cache_usage(&self) -> usize {
+        unsafe {
+            rocksdb_ffi::rocksdb_write_buffer_manager_dummy_entries_in_cache_usage(
+                self.inner.0) as usize
+        }
+    }
+
+    pub fn buffer_size(&self) -> usize {
+        unsafe {
+            rocksdb_ffi::rocksdb_write_buffer_manager_buffer_size(
+                self.inner.0) as usize
+        }
+    }
+
+    pub fn set_buffer_size(&self, buffer_size: usize) {
+        unsafe {
+            rocksdb_ffi::rocksdb_write_buffer_manager_set_buffer_size(
+                self.inner.0,
+                buffer_size as size_t);
+        }
+    }
+
+    pub fn set_allow_stall(&self, allow_stall: bool) {
+        unsafe {
+            rocksdb_ffi::rocksdb_write_buffer_manager_set_allow_stall(
+                self.inner.0,
+                allow_stall);
+        }
+    }
+
+    pub fn inner(&self) -> rocksdb_ffi::DBWriteBufferManager {
+        self.inner.0
+    }
+}
@@ -3,3 +3,23 @@ extern crate rocksdb;
 mod test_iterator;
 mod test_multithreaded;
 mod test_column_family;
+mod test_compaction;
+mod test_property;
+mod test_statistics;
+mod test_approximate_sizes;
+mod test_checkpoint;
+mod test_backup;
+mod test_read_only;
+mod test_secondary;
+mod test_ttl;
+mod test_transaction;
+mod test_sst_file_writer;
+mod test_block_based_options;
+mod test_compression;
+mod test_compaction_style;
+mod test_rate_limiter;
+mod test_write_buffer_manager;
+mod test_options_file;
+mod test_options;
+mod test_paths;
+mod test_wal;
@@ -0,0 +1,60 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use rocksdb::{DB, Options, Range, SizeApproximationFlags, Writable};
+
+#[test]
+pub fn test_approximate_sizes() {
+    let path = "_rust_rocksdb_approximatesizestest";
+    {
+        let mut db = DB::open_default(path).unwrap();
+        let cf1 = db.create_cf("cf1", &Options::new()).unwrap();
+        let value = vec![b'x'; 1024];
+        for i in 0..1000 {
+            let key = format!("a{:04}", i);
+            db.put(key.as_bytes(), &value).unwrap();
+            db.put_cf(cf1, key.as_bytes(), &value).unwrap();
+        }
+
+        // everything is still in the memtable
+        let memtable = db.approximate_memtable_stats(Range::new(b"a", b"b"))
+                         .unwrap();
+        assert!(memtable > 0);
+        let sizes = db.approximate_sizes(&[Range::new(b"a", b"b")]).unwrap();
+        assert_eq!(sizes, vec![0]);
+
+        db.flush().unwrap();
+        db.flush_cf(cf1).unwrap();
+
+        let sizes = db.approximate_sizes(&[Range::new(b"a", b"b"),
+                                           Range::new(b"b", b"c")])
+                      .unwrap();
+        assert_eq!(sizes.len(), 2);
+        assert!(sizes[0] > 0);
+        assert_eq!(sizes[1], 0);
+
+        let sizes = db.approximate_sizes_cf(cf1, &[Range::new(b"a", b"b")])
+                      .unwrap();
+        assert!(sizes[0] > 0);
+
+        let nothing = SizeApproximationFlags {
+            include_memtable: false,
+            include_files: false,
+        };
+        assert!(db.approximate_sizes_opt(&[Range::new(b"a", b"b")], nothing)
+                  .is_err());
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
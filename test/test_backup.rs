@@ -0,0 +1,129 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use rocksdb::{BackupEngine, BackupEngineOptions, DB, Env, Options,
+              RestoreOption, ShareFilesNaming, Writable};
+
+#[test]
+pub fn test_backup_management() {
+    let path = "_rust_rocksdb_backuptest";
+    let backup_path = "_rust_rocksdb_backuptest_backups";
+    let restore_path = "_rust_rocksdb_backuptest_restore";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let db = DB::open(&opts, path).unwrap();
+        let engine = BackupEngine::new(&opts, backup_path).unwrap();
+        for i in 0..2 {
+            db.put(b"k1", format!("v{}", i).as_bytes()).unwrap();
+            engine.create_new_backup(&db).unwrap();
+        }
+        db.put(b"k1", b"v2").unwrap();
+        engine.create_new_backup_flush(&db, false).unwrap();
+
+        let info = engine.get_backup_info().unwrap();
+        assert_eq!(info.len(), 3);
+        for backup in info.iter() {
+            assert!(backup.timestamp > 0);
+            assert!(backup.size > 0);
+            assert!(backup.num_files > 0);
+            assert!(engine.verify_backup(backup.backup_id).is_ok());
+        }
+        assert!(engine.verify_backup(42).is_err());
+
+        engine.purge_old_backups(2).unwrap();
+        let info = engine.get_backup_info().unwrap();
+        assert_eq!(info.len(), 2);
+        let oldest = info.iter().map(|b| b.backup_id).min().unwrap();
+
+        engine.restore_from_backup(oldest, restore_path, restore_path, false)
+              .unwrap();
+        {
+            let restored = DB::open_default(restore_path).unwrap();
+            assert!(restored.get(b"k1").unwrap().unwrap().to_utf8().unwrap() ==
+                    "v1");
+        }
+
+        // the newest backup skipped the flush, so k1 comes back via the WAL
+        engine.restore_from_latest_backup(restore_path, restore_path, false)
+              .unwrap();
+        let restored = DB::open_default(restore_path).unwrap();
+        assert!(restored.get(b"k1").unwrap().unwrap().to_utf8().unwrap() ==
+                "v2");
+        assert!(engine.restore_from_latest_backup("bad\0path", restore_path,
+                                                  false)
+                      .is_err());
+        drop(restored);
+
+        let mut restore_opts = RestoreOption::new();
+        restore_opts.set_keep_log_files(false);
+        engine.restore_from_backup_opt(oldest,
+                                       restore_path,
+                                       restore_path,
+                                       &restore_opts)
+              .unwrap();
+        assert!(engine.restore_from_backup_opt(oldest,
+                                               restore_path,
+                                               "bad\0path",
+                                               &restore_opts)
+                      .is_err());
+        engine.restore_from_latest_backup_opt(restore_path,
+                                              restore_path,
+                                              &restore_opts)
+              .unwrap();
+    }
+    assert!(DB::destroy(&Options::new(), restore_path).is_ok());
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+    let _ = ::std::fs::remove_dir_all(backup_path);
+}
+
+#[test]
+pub fn test_backup_engine_options() {
+    let path = "_rust_rocksdb_backupoptionstest";
+    let backup_path = "_rust_rocksdb_backupoptionstest_backups";
+    let restore_path = "_rust_rocksdb_backupoptionstest_restore";
+    {
+        let db = DB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let mut backup_opts = BackupEngineOptions::new("unused").unwrap();
+        backup_opts.set_backup_dir(backup_path).unwrap();
+        // keep the backups in memory, so nothing lands in backup_path
+        backup_opts.set_backup_env(&Env::mem_env());
+        backup_opts.set_share_table_files(true);
+        backup_opts.set_share_files_with_checksum_naming(
+            ShareFilesNaming::UseDbSessionId, false);
+        backup_opts.set_sync(false);
+        backup_opts.set_destroy_old_data(true);
+        backup_opts.set_backup_log_files(true);
+        backup_opts.set_backup_rate_limit(64 << 20);
+        backup_opts.set_restore_rate_limit(64 << 20);
+        backup_opts.set_max_background_operations(2);
+
+        let engine = BackupEngine::open(&backup_opts, &Env::new()).unwrap();
+        drop(backup_opts);
+        engine.create_new_backup(&db).unwrap();
+        assert_eq!(engine.get_backup_info().unwrap().len(), 1);
+        assert!(!::std::path::Path::new(backup_path).exists());
+
+        engine.restore_from_latest_backup(restore_path, restore_path, false)
+              .unwrap();
+        let restored = DB::open_default(restore_path).unwrap();
+        assert!(restored.get(b"k1").unwrap().unwrap().to_utf8().unwrap() ==
+                "v1");
+    }
+    assert!(DB::destroy(&Options::new(), restore_path).is_ok());
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
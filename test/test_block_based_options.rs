@@ -0,0 +1,80 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use rocksdb::{BlockBasedOptions, Cache, DB, FilterPolicy, Options, Writable};
+
+fn open_with_table_options(path: &str, cache: &Cache) -> DB {
+    let mut block_opts = BlockBasedOptions::new();
+    block_opts.set_block_cache(cache);
+    block_opts.set_filter_policy(&FilterPolicy::bloom_full(10.0));
+    block_opts.set_block_size(4096);
+    block_opts.set_block_size_deviation(10);
+    block_opts.set_block_restart_interval(16);
+    block_opts.set_whole_key_filtering(true);
+    block_opts.set_cache_index_and_filter_blocks(true);
+    block_opts.set_cache_index_and_filter_blocks_with_high_priority(true);
+    block_opts.set_pin_l0_filter_and_index_blocks_in_cache(true);
+    block_opts.set_format_version(5);
+
+    let mut opts = Options::new();
+    opts.create_if_missing(true);
+    opts.set_block_based_table_factory(&block_opts);
+    DB::open(&opts, path).unwrap()
+}
+
+#[test]
+pub fn test_shared_block_cache() {
+    let path1 = "_rust_rocksdb_blockbasedoptionstest_1";
+    let path2 = "_rust_rocksdb_blockbasedoptionstest_2";
+    {
+        let cache = Cache::new_lru_cache_with_shard_bits(8 << 20, 4);
+        assert_eq!(cache.get_capacity(), 8 << 20);
+        assert_eq!(cache.get_usage(), 0);
+
+        let db1 = open_with_table_options(path1, &cache);
+        let db2 = open_with_table_options(path2, &cache.clone());
+        // the databases keep the cache alive on their own
+        drop(cache);
+
+        for db in &[&db1, &db2] {
+            for i in 0..100 {
+                db.put(format!("k{}", i).as_bytes(), b"value").unwrap();
+            }
+            db.flush().unwrap();
+            assert!(db.get(b"k1").unwrap().unwrap().to_utf8().unwrap() ==
+                    "value");
+            assert!(db.get(b"missing").unwrap().is_none());
+        }
+    }
+    assert!(DB::destroy(&Options::new(), path1).is_ok());
+    assert!(DB::destroy(&Options::new(), path2).is_ok());
+}
+
+#[test]
+pub fn test_cache_usage() {
+    let path = "_rust_rocksdb_blockbasedoptionstest_usage";
+    {
+        let cache = Cache::new_lru_cache(8 << 20);
+        let db = open_with_table_options(path, &cache);
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert!(db.get(b"k1").unwrap().is_some());
+        assert!(cache.get_usage() > 0);
+
+        cache.set_capacity(4 << 20);
+        assert_eq!(cache.get_capacity(), 4 << 20);
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
@@ -0,0 +1,42 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use rocksdb::{Checkpoint, DB, Options, Writable};
+
+#[test]
+pub fn test_checkpoint() {
+    let path = "_rust_rocksdb_checkpointtest";
+    let checkpoint_path = "_rust_rocksdb_checkpointtest_checkpoint";
+    {
+        let db = DB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        let checkpoint = Checkpoint::new(&db).unwrap();
+        checkpoint.create_checkpoint(checkpoint_path).unwrap();
+        // the target directory must not exist yet
+        assert!(checkpoint.create_checkpoint(checkpoint_path).is_err());
+
+        db.put(b"k3", b"v3").unwrap();
+        db.delete(b"k1").unwrap();
+
+        let copy = DB::open_default(checkpoint_path).unwrap();
+        assert!(copy.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "v1");
+        assert!(copy.get(b"k2").unwrap().unwrap().to_utf8().unwrap() == "v2");
+        assert!(copy.get(b"k3").unwrap().is_none());
+    }
+    assert!(DB::destroy(&Options::new(), checkpoint_path).is_ok());
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
@@ -114,6 +114,24 @@ pub fn test_column_family() {
     assert!(DB::destroy(&Options::new(), path).is_ok());
 }
 
+#[test]
+pub fn test_column_family_invalid_name() {
+    let path = "_rust_rocksdb_cftest_invalid_name";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        match DB::open_cf(&opts, path, &["cf\0"]) {
+            Ok(_) => panic!("opened a column family with a NUL in its name"),
+            Err(e) => {
+                assert_eq!(e,
+                           "Failed to convert column family name \"cf\\0\" \
+                            to CString")
+            }
+        }
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
 fn test_provided_merge(_: &[u8],
                        existing_val: Option<&[u8]>,
                        operands: &mut MergeOperands)
@@ -0,0 +1,137 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use rocksdb::{BottommostLevelCompaction, CompactRangeOptions, DB, Decision,
+              FlushOptions, Options, Writable};
+
+fn expire_and_redact(_: u32, key: &[u8], value: &[u8]) -> Decision {
+    if value == b"expired" {
+        Decision::Remove
+    } else if key.starts_with(b"secret") {
+        Decision::ChangeValue(b"redacted".to_vec())
+    } else {
+        Decision::Keep
+    }
+}
+
+#[test]
+pub fn test_compact_range() {
+    let path = "_rust_rocksdb_compactrangetest";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let mut db = DB::open(&opts, path).unwrap();
+        let cf1 = db.create_cf("cf1", &Options::new()).unwrap();
+
+        for i in 0..100 {
+            let key = format!("k{}", i);
+            db.put(key.as_bytes(), b"value").unwrap();
+            db.put_cf(cf1, key.as_bytes(), b"value").unwrap();
+        }
+        db.flush().unwrap();
+        db.flush_cf(cf1).unwrap();
+        db.flush_wal(true).unwrap();
+
+        for i in 0..50 {
+            let key = format!("k{}", i);
+            db.delete(key.as_bytes()).unwrap();
+        }
+        let mut flush_opts = FlushOptions::new();
+        flush_opts.set_wait(true);
+        db.flush_opt(&flush_opts).unwrap();
+
+        db.compact_range(None, None);
+        db.compact_range(Some(b"k1"), Some(b"k5"));
+        db.compact_range_cf(cf1, None, Some(b"k5"));
+
+        let mut compact_opts = CompactRangeOptions::new();
+        compact_opts.set_exclusive_manual_compaction(true);
+        compact_opts.set_bottommost_level_compaction(
+            BottommostLevelCompaction::Force);
+        compact_opts.set_change_level(true);
+        compact_opts.set_target_level(1);
+        db.compact_range_opt(&compact_opts, None, None);
+        db.compact_range_cf_opt(cf1, &compact_opts, None, None);
+
+        assert!(db.get(b"k1").unwrap().is_none());
+        assert!(db.get(b"k75").unwrap().unwrap().to_utf8().unwrap() ==
+                "value");
+        assert!(db.get_cf(cf1, b"k1").unwrap().is_some());
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_compaction_filter() {
+    let path = "_rust_rocksdb_expirefiltertest";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        opts.set_compaction_filter("expire and redact", expire_and_redact);
+        let db = DB::open(&opts, path).unwrap();
+        db.put(b"k1", b"fresh").unwrap();
+        db.put(b"k2", b"expired").unwrap();
+        db.put(b"secret1", b"hunter2").unwrap();
+        db.flush().unwrap();
+
+        let mut compact_opts = CompactRangeOptions::new();
+        compact_opts.set_bottommost_level_compaction(
+            BottommostLevelCompaction::Force);
+        db.compact_range_opt(&compact_opts, None, None);
+
+        assert!(db.get(b"k1").unwrap().unwrap().to_utf8().unwrap() ==
+                "fresh");
+        assert!(db.get(b"k2").unwrap().is_none());
+        assert!(db.get(b"secret1").unwrap().unwrap().to_utf8().unwrap() ==
+                "redacted");
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_compaction_filter_with_column_families() {
+    let path = "_rust_rocksdb_expirefiltertest_cf";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let mut db = DB::open(&opts, path).unwrap();
+        db.create_cf("cf1", &Options::new()).unwrap();
+    }
+    {
+        // the filter set on `opts` also applies to the default column
+        // family when other column families are opened alongside it
+        let mut opts = Options::new();
+        opts.set_compaction_filter("expire and redact", expire_and_redact);
+        let db = DB::open_cf(&opts, path, &["cf1"]).unwrap();
+        let cf1 = *db.cf_handle("cf1").unwrap();
+        db.put(b"k1", b"fresh").unwrap();
+        db.put(b"k2", b"expired").unwrap();
+        db.put_cf(cf1, b"k2", b"expired").unwrap();
+        db.flush().unwrap();
+        db.flush_cf(cf1).unwrap();
+
+        let mut compact_opts = CompactRangeOptions::new();
+        compact_opts.set_bottommost_level_compaction(
+            BottommostLevelCompaction::Force);
+        db.compact_range_opt(&compact_opts, None, None);
+        db.compact_range_cf_opt(cf1, &compact_opts, None, None);
+
+        assert!(db.get(b"k1").unwrap().unwrap().to_utf8().unwrap() ==
+                "fresh");
+        assert!(db.get(b"k2").unwrap().is_none());
+        assert!(db.get_cf(cf1, b"k2").unwrap().is_none());
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
@@ -0,0 +1,99 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use std::thread;
+use std::time::Duration;
+
+use rocksdb::{DB, DBCompactionStyle, DBCompressionType,
+              DBUniversalCompactionStyle, FifoCompactionOptions, Options,
+              UniversalCompactionOptions, Writable};
+
+#[test]
+pub fn test_universal_compaction() {
+    let path = "_rust_rocksdb_universalcompactiontest";
+    {
+        let mut uco = UniversalCompactionOptions::new();
+        uco.set_size_ratio(2);
+        uco.set_min_merge_width(2);
+        uco.set_max_merge_width(4);
+        uco.set_max_size_amplification_percent(150);
+        uco.set_compression_size_percent(-1);
+        uco.set_stop_style(
+            DBUniversalCompactionStyle::rocksdb_total_size_compaction_stop_style);
+
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        opts.set_compaction_style(DBCompactionStyle::DBUniversalCompaction);
+        opts.set_universal_compaction_options(&uco);
+        // the options are copied, so they don't have to outlive `opts`
+        drop(uco);
+
+        let db = DB::open(&opts, path).unwrap();
+        for round in 0..5 {
+            for i in 0..100 {
+                let value = format!("v{}", round);
+                db.put(format!("k{}", i).as_bytes(), value.as_bytes()).unwrap();
+            }
+            db.flush().unwrap();
+        }
+        db.compact_range(None, None);
+        for i in 0..100 {
+            let value = db.get(format!("k{}", i).as_bytes()).unwrap().unwrap();
+            assert_eq!(value.to_utf8().unwrap(), "v4");
+        }
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_fifo_compaction() {
+    let path = "_rust_rocksdb_fifocompactiontest";
+    {
+        let mut fifo = FifoCompactionOptions::new();
+        fifo.set_max_table_files_size(64 << 10);
+        fifo.set_allow_compaction(false);
+
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        opts.set_compaction_style(DBCompactionStyle::DBFifoCompaction);
+        opts.set_fifo_compaction_options(&fifo);
+        // the values below compress to almost nothing
+        opts.set_compression_type(DBCompressionType::DBNoCompression);
+
+        let db = DB::open(&opts, path).unwrap();
+        let value = "x".repeat(1 << 10);
+        for batch in 0..10 {
+            for i in 0..32 {
+                let key = format!("k{:02}{:02}", batch, i);
+                db.put(key.as_bytes(), value.as_bytes()).unwrap();
+            }
+            db.flush().unwrap();
+        }
+
+        // the oldest files are dropped by a background compaction once the
+        // total goes over the limit
+        let mut dropped = false;
+        for _ in 0..100 {
+            if db.get(b"k0000").unwrap().is_none() {
+                dropped = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        assert!(dropped);
+        assert!(db.get(b"k0931").unwrap().is_some());
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
@@ -0,0 +1,99 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use rocksdb::{DB, DBCompressionType, Options, Writable};
+
+const ALL_TYPES: [DBCompressionType; 8] =
+    [DBCompressionType::DBNoCompression,
+     DBCompressionType::DBSnappyCompression,
+     DBCompressionType::DBZlibCompression,
+     DBCompressionType::DBBz2Compression,
+     DBCompressionType::DBLz4Compression,
+     DBCompressionType::DBLz4hcCompression,
+     DBCompressionType::DBXpressCompression,
+     DBCompressionType::DBZstdCompression];
+
+// Which codecs are compiled in depends on how the linked RocksDB was built,
+// and opening fails up front for those that aren't. Any other error fails
+// the test.
+fn open(opts: &Options, path: &str) -> Option<DB> {
+    match DB::open(opts, path) {
+        Ok(db) => Some(db),
+        Err(e) => {
+            assert!(e.contains("not linked with the binary"), "{}", e);
+            None
+        }
+    }
+}
+
+fn compression_opts(t: DBCompressionType) -> Options {
+    let mut opts = Options::new();
+    opts.create_if_missing(true);
+    opts.set_compression_type(t);
+    opts.set_bottommost_compression(t);
+    opts.set_compression_options(-14, 6, 0, 0);
+    opts
+}
+
+fn write_and_read_back(db: &DB) {
+    for i in 0..1000 {
+        let value = format!("value {} {}", i, "x".repeat(100));
+        db.put(format!("k{:04}", i).as_bytes(), value.as_bytes()).unwrap();
+    }
+    db.flush().unwrap();
+    db.compact_range(None, None);
+    for i in 0..1000 {
+        let value = db.get(format!("k{:04}", i).as_bytes()).unwrap().unwrap();
+        assert_eq!(value.to_utf8().unwrap(),
+                   format!("value {} {}", i, "x".repeat(100)));
+    }
+}
+
+#[test]
+pub fn test_compression_types() {
+    let path = "_rust_rocksdb_compressiontest";
+    for &t in ALL_TYPES.iter() {
+        {
+            match open(&compression_opts(t), path) {
+                Some(db) => write_and_read_back(&db),
+                // no compression is always available
+                None => assert!(t != DBCompressionType::DBNoCompression),
+            }
+        }
+        assert!(DB::destroy(&Options::new(), path).is_ok());
+    }
+}
+
+#[test]
+pub fn test_compression_per_level_and_dictionary() {
+    let path = "_rust_rocksdb_compressiontest_per_level";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        opts.set_compression_per_level(&[DBCompressionType::DBNoCompression,
+                                         DBCompressionType::DBNoCompression,
+                                         DBCompressionType::DBZstdCompression]);
+        opts.set_bottommost_compression(DBCompressionType::DBZstdCompression);
+        opts.set_compression_options(-14, 3, 0, 16 << 10);
+        opts.set_zstd_max_train_bytes(100 << 10);
+        opts.set_bottommost_compression_options(-14, 19, 0, 16 << 10);
+        opts.set_bottommost_zstd_max_train_bytes(100 << 10);
+        // only runs when the linked RocksDB has zstd
+        if let Some(db) = open(&opts, path) {
+            write_and_read_back(&db);
+        }
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
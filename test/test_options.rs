@@ -0,0 +1,74 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use rocksdb::{DBCompactionStyle, DBCompressionType, Options};
+
+#[test]
+pub fn test_options_getters() {
+    let mut opts = Options::new();
+    opts.create_if_missing(true);
+    opts.set_write_buffer_size(16 << 20);
+    opts.set_max_write_buffer_number(4);
+    opts.set_min_write_buffer_number_to_merge(2);
+    opts.set_max_open_files(500);
+    opts.set_target_file_size_base(32 << 20);
+    opts.set_level_zero_slowdown_writes_trigger(30);
+    opts.set_level_zero_stop_writes_trigger(40);
+    opts.set_compaction_style(DBCompactionStyle::DBUniversalCompaction);
+    opts.set_compression_type(DBCompressionType::DBLz4Compression);
+    opts.set_bottommost_compression(DBCompressionType::DBZstdCompression);
+    opts.set_max_background_compactions(3);
+    opts.set_max_background_flushes(2);
+
+    assert!(opts.get_create_if_missing());
+    assert_eq!(opts.get_write_buffer_size(), 16 << 20);
+    assert_eq!(opts.get_max_write_buffer_number(), 4);
+    assert_eq!(opts.get_min_write_buffer_number_to_merge(), 2);
+    assert_eq!(opts.get_max_open_files(), 500);
+    assert_eq!(opts.get_target_file_size_base(), 32 << 20);
+    assert_eq!(opts.get_level_zero_slowdown_writes_trigger(), 30);
+    assert_eq!(opts.get_level_zero_stop_writes_trigger(), 40);
+    assert_eq!(opts.get_compaction_style(),
+               DBCompactionStyle::DBUniversalCompaction);
+    assert_eq!(opts.get_compression_type(),
+               Some(DBCompressionType::DBLz4Compression));
+    assert_eq!(opts.get_bottommost_compression(),
+               Some(DBCompressionType::DBZstdCompression));
+    assert_eq!(opts.get_max_background_compactions(), 3);
+    assert_eq!(opts.get_max_background_flushes(), 2);
+}
+
+#[test]
+pub fn test_options_defaults() {
+    let opts = Options::new();
+    assert!(!opts.get_create_if_missing());
+    assert_eq!(opts.get_compaction_style(),
+               DBCompactionStyle::DBLevelCompaction);
+    assert_eq!(opts.get_bottommost_compression(), None);
+    assert_eq!(opts.get_num_levels(), 7);
+    assert_eq!(opts.get_level_zero_file_num_compaction_trigger(), 4);
+}
+
+#[test]
+pub fn test_options_debug() {
+    let mut opts = Options::new();
+    opts.set_write_buffer_size(16 << 20);
+    opts.set_compaction_style(DBCompactionStyle::DBFifoCompaction);
+    let debug = format!("{:?}", opts);
+    assert!(debug.starts_with("Options {"), "{}", debug);
+    assert!(debug.contains("write_buffer_size: 16777216"), "{}", debug);
+    assert!(debug.contains("compaction_style: DBFifoCompaction"), "{}", debug);
+    assert!(debug.contains("bottommost_compression: None"), "{}", debug);
+}
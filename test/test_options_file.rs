@@ -0,0 +1,62 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use rocksdb::{DB, Options, Writable};
+
+#[test]
+pub fn test_load_latest_options() {
+    let path = "_rust_rocksdb_loadlatestoptionstest";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        opts.set_max_open_files(100);
+        let mut db = DB::open(&opts, path).unwrap();
+        let mut cf_opts = Options::new();
+        cf_opts.set_write_buffer_size(8 << 20);
+        db.create_cf("cf1", &cf_opts).unwrap();
+    }
+    {
+        let (opts, cfs) = Options::load_latest(path).unwrap();
+        assert_eq!(opts.get_max_open_files(), 100);
+        let names: Vec<&str> = cfs.iter().map(|cf| cf.name.as_str()).collect();
+        assert_eq!(names, vec!["default", "cf1"]);
+        assert_eq!(cfs[1].options.get_write_buffer_size(), 8 << 20);
+
+        let db = DB::open_cf_descriptors(&opts, path, cfs).unwrap();
+        let cf1 = *db.cf_handle("cf1").unwrap();
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+        assert!(db.get_cf(cf1, b"k1").unwrap().unwrap().to_utf8().unwrap() ==
+                "v1");
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_load_latest_options_missing_db() {
+    let path = "_rust_rocksdb_loadlatestoptionstest_missing";
+    assert!(Options::load_latest(path).is_err());
+}
+
+#[test]
+pub fn test_options_from_string() {
+    let mut base = Options::new();
+    base.set_max_open_files(100);
+    let opts = Options::from_string(&base, "write_buffer_size=4194304").unwrap();
+    assert_eq!(opts.get_write_buffer_size(), 4194304);
+    // unmentioned options are taken from the base
+    assert_eq!(opts.get_max_open_files(), 100);
+
+    assert!(Options::from_string(&base, "no_such_option=1").is_err());
+}
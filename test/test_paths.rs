@@ -0,0 +1,105 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use std::fs;
+use std::path::Path;
+
+use rocksdb::{ColumnFamilyDescriptor, DB, Options, Writable};
+
+fn has_sst(dir: &str) -> bool {
+    fs::read_dir(dir)
+        .unwrap()
+        .any(|e| e.unwrap().path().extension().map_or(false, |ext| ext == "sst"))
+}
+
+fn has_wal(dir: &str) -> bool {
+    fs::read_dir(dir)
+        .unwrap()
+        .any(|e| e.unwrap().path().extension().map_or(false, |ext| ext == "log"))
+}
+
+#[test]
+pub fn test_wal_dir_and_db_paths() {
+    let root = "_rust_rocksdb_pathstest";
+    let path = "_rust_rocksdb_pathstest/db";
+    let wal_dir = "_rust_rocksdb_pathstest/nvme/wal";
+    let log_dir = "_rust_rocksdb_pathstest/logs";
+    let data_dir = "_rust_rocksdb_pathstest/hdd/data";
+    let cf_dir = "_rust_rocksdb_pathstest/hdd/cf1";
+    let opts = || {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        opts.set_wal_dir(wal_dir).unwrap();
+        opts.set_db_log_dir(log_dir).unwrap();
+        opts.set_db_paths(&[(data_dir, 1 << 30)]).unwrap();
+        opts
+    };
+    {
+        let mut db = DB::open(&opts(), path).unwrap();
+        db.create_cf("cf1", &Options::new()).unwrap();
+    }
+    {
+        let mut cf_opts = Options::new();
+        cf_opts.set_cf_paths(&[(cf_dir, 1 << 30)]).unwrap();
+        let cfs = vec![ColumnFamilyDescriptor::new("cf1", cf_opts)];
+        let db = DB::open_cf_descriptors(&opts(), path, cfs).unwrap();
+        let cf1 = *db.cf_handle("cf1").unwrap();
+
+        // the nested directories are created before RocksDB opens them
+        assert!(Path::new(wal_dir).is_dir());
+        assert!(Path::new(log_dir).is_dir());
+        assert!(Path::new(data_dir).is_dir());
+        assert!(Path::new(cf_dir).is_dir());
+
+        db.put(b"k1", b"v1").unwrap();
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+        assert!(has_wal(wal_dir));
+        assert!(!has_wal(path));
+        db.flush().unwrap();
+        db.flush_cf(cf1).unwrap();
+        assert!(has_sst(data_dir));
+        assert!(has_sst(cf_dir));
+        assert!(!has_sst(path));
+    }
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+pub fn test_create_cf_with_cf_paths() {
+    let root = "_rust_rocksdb_cfpathstest";
+    let path = "_rust_rocksdb_cfpathstest/db";
+    let cf_dir = "_rust_rocksdb_cfpathstest/hdd/cf1";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let mut db = DB::open(&opts, path).unwrap();
+        let mut cf_opts = Options::new();
+        cf_opts.set_cf_paths(&[(cf_dir, 1 << 30)]).unwrap();
+        let cf1 = db.create_cf("cf1", &cf_opts).unwrap();
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+        db.flush_cf(cf1).unwrap();
+        assert!(has_sst(cf_dir));
+    }
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+pub fn test_invalid_paths() {
+    let mut opts = Options::new();
+    assert!(opts.set_wal_dir("wal\0dir").is_err());
+    assert!(opts.set_db_log_dir("log\0dir").is_err());
+    assert!(opts.set_db_paths(&[("data\0dir", 1 << 30)]).is_err());
+    assert!(opts.set_cf_paths(&[("cf\0dir", 1 << 30)]).is_err());
+}
@@ -0,0 +1,51 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use rocksdb::{DB, Options, Property, Writable};
+
+#[test]
+pub fn test_property() {
+    let path = "_rust_rocksdb_propertytest";
+    {
+        let mut db = DB::open_default(path).unwrap();
+        let cf1 = db.create_cf("cf1", &Options::new()).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let stats = db.property_value(Property::Stats).unwrap().unwrap();
+        assert!(stats.contains("Compaction Stats"));
+        assert!(db.property_value("rocksdb.stats").unwrap().is_some());
+        assert!(db.property_value("rocksdb.does-not-exist")
+                  .unwrap()
+                  .is_none());
+        assert!(db.property_value_cf(cf1, Property::CFStats)
+                  .unwrap()
+                  .is_some());
+
+        let keys = db.property_int_value(Property::EstimateNumKeys).unwrap();
+        assert_eq!(keys, Some(1));
+        let mem = db.property_int_value(Property::CurSizeAllMemTables)
+                    .unwrap()
+                    .unwrap();
+        assert!(mem > 0);
+        db.flush().unwrap();
+        let files = db.property_value(Property::NumFilesAtLevel(0)).unwrap();
+        assert_eq!(files, Some("1".to_string()));
+        assert_eq!(db.property_int_value_cf(cf1, Property::EstimateNumKeys)
+                     .unwrap(),
+                   Some(0));
+        assert!(db.property_int_value(Property::Stats).unwrap().is_none());
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
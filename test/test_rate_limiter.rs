@@ -0,0 +1,58 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use rocksdb::{DB, Options, RateLimiter, Writable};
+
+fn write_and_compact(db: &DB) {
+    for i in 0..100 {
+        db.put(format!("k{}", i).as_bytes(), b"value").unwrap();
+    }
+    db.flush().unwrap();
+    db.compact_range(None, None);
+    assert!(db.get(b"k99").unwrap().unwrap().to_utf8().unwrap() == "value");
+}
+
+#[test]
+pub fn test_ratelimiter() {
+    let path = "_rust_rocksdb_ratelimitertest";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        opts.set_ratelimiter(16 << 20, 100 * 1000, 10);
+        let db = DB::open(&opts, path).unwrap();
+        write_and_compact(&db);
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_shared_rate_limiter() {
+    let path1 = "_rust_rocksdb_sharedratelimitertest1";
+    let path2 = "_rust_rocksdb_sharedratelimitertest2";
+    {
+        let limiter = RateLimiter::new_auto_tuned(16 << 20, 100 * 1000, 10);
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        opts.set_rate_limiter(&limiter);
+        let db1 = DB::open(&opts, path1).unwrap();
+        let db2 = DB::open(&opts, path2).unwrap();
+        // the databases keep their own reference to the limiter
+        drop(limiter);
+        write_and_compact(&db1);
+        write_and_compact(&db2);
+    }
+    assert!(DB::destroy(&Options::new(), path1).is_ok());
+    assert!(DB::destroy(&Options::new(), path2).is_ok());
+}
@@ -0,0 +1,60 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use std::path::Path;
+
+use rocksdb::{DB, IteratorMode, Options, Writable};
+
+#[test]
+pub fn test_read_only() {
+    let path = "_rust_rocksdb_readonlytest";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let mut db = DB::open(&opts, path).unwrap();
+        db.create_cf("cf1", &Options::new()).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        let cf1 = *db.cf_handle("cf1").unwrap();
+        db.put_cf(cf1, b"k2", b"v2").unwrap();
+        db.flush().unwrap();
+
+        // a read-only instance can sit alongside the writer
+        let ro = DB::open_for_read_only(&Options::new(), path, false).unwrap();
+        assert!(ro.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "v1");
+        assert!(ro.cf_handle("cf1").is_none());
+
+        let ro = DB::open_cf_for_read_only(&Options::new(), path, &["cf1"], false)
+                     .unwrap();
+        let cf1 = *ro.cf_handle("cf1").unwrap();
+        assert!(ro.get_cf(cf1, b"k2").unwrap().unwrap().to_utf8().unwrap() ==
+                "v2");
+        assert_eq!(ro.iterator_cf(cf1, IteratorMode::Start)
+                     .unwrap()
+                     .count(),
+                   1);
+
+        // writes made after the read-only open aren't visible to it
+        db.put(b"k3", b"v3").unwrap();
+        assert!(ro.get(b"k3").unwrap().is_none());
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_read_only_does_not_create() {
+    let path = "_rust_rocksdb_readonlytest_missing";
+    assert!(DB::open_for_read_only(&Options::new(), path, false).is_err());
+    assert!(!Path::new(path).exists());
+}
@@ -0,0 +1,82 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use std::env;
+use std::fs;
+
+use rocksdb::{DB, Options, Writable};
+
+#[test]
+pub fn test_secondary() {
+    let root = env::temp_dir().join("_rust_rocksdb_secondarytest");
+    let primary_path = root.join("primary");
+    let secondary_path = root.join("secondary");
+    let primary_path = primary_path.to_str().unwrap();
+    let secondary_path = secondary_path.to_str().unwrap();
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        opts.set_max_open_files(-1);
+        let mut primary = DB::open(&opts, primary_path).unwrap();
+        primary.create_cf("cf1", &Options::new()).unwrap();
+        primary.put(b"k1", b"v1").unwrap();
+
+        let mut opts = Options::new();
+        opts.set_max_open_files(-1);
+        let secondary = DB::open_cf_as_secondary(&opts,
+                                                 primary_path,
+                                                 secondary_path,
+                                                 &["cf1"])
+                            .unwrap();
+        assert!(secondary.get(b"k1").unwrap().unwrap().to_utf8().unwrap() ==
+                "v1");
+
+        // new writes only show up once the secondary catches up, whether
+        // they are still in the WAL or already flushed
+        let cf1 = *primary.cf_handle("cf1").unwrap();
+        primary.put(b"k2", b"v2").unwrap();
+        primary.put_cf(cf1, b"k3", b"v3").unwrap();
+        assert!(secondary.get(b"k2").unwrap().is_none());
+        secondary.try_catch_up_with_primary().unwrap();
+        assert!(secondary.get(b"k2").unwrap().unwrap().to_utf8().unwrap() ==
+                "v2");
+        let secondary_cf1 = *secondary.cf_handle("cf1").unwrap();
+        assert!(secondary.get_cf(secondary_cf1, b"k3")
+                         .unwrap()
+                         .unwrap()
+                         .to_utf8()
+                         .unwrap() == "v3");
+
+        primary.put(b"k4", b"v4").unwrap();
+        primary.flush().unwrap();
+        secondary.try_catch_up_with_primary().unwrap();
+        assert!(secondary.get(b"k4").unwrap().unwrap().to_utf8().unwrap() ==
+                "v4");
+    }
+    assert!(DB::destroy(&Options::new(), primary_path).is_ok());
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+pub fn test_secondary_requires_primary() {
+    let root = env::temp_dir().join("_rust_rocksdb_secondarytest_missing");
+    let primary_path = root.join("primary");
+    let secondary_path = root.join("secondary");
+    assert!(DB::open_as_secondary(&Options::new(),
+                                  primary_path.to_str().unwrap(),
+                                  secondary_path.to_str().unwrap())
+                .is_err());
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,88 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use std::fs;
+
+use rocksdb::{DB, IngestExternalFileOptions, Options, SstFileWriter, Writable};
+
+#[test]
+pub fn test_sst_file_writer_ingest() {
+    let path = "_rust_rocksdb_sstfilewritertest";
+    let sst_path = "_rust_rocksdb_sstfilewritertest_1.sst";
+    let sst_cf_path = "_rust_rocksdb_sstfilewritertest_2.sst";
+    {
+        let opts = Options::new();
+        let mut writer = SstFileWriter::create(&opts);
+        writer.open(sst_path).unwrap();
+        writer.put(b"k1", b"v1").unwrap();
+        writer.put(b"k2", b"v2").unwrap();
+        writer.delete(b"k3").unwrap();
+        // keys have to be added in order
+        assert!(writer.put(b"k0", b"v0").is_err());
+        writer.finish().unwrap();
+        assert!(writer.file_size() > 0);
+
+        writer.open(sst_cf_path).unwrap();
+        writer.put(b"k4", b"v4").unwrap();
+        writer.finish().unwrap();
+
+        let mut db_opts = Options::new();
+        db_opts.create_if_missing(true);
+        let mut db = DB::open(&db_opts, path).unwrap();
+        db.put(b"k3", b"old").unwrap();
+        let cf1 = db.create_cf("cf1", &Options::new()).unwrap();
+
+        let mut ingest_opts = IngestExternalFileOptions::new();
+        ingest_opts.set_move_files(true);
+        db.ingest_external_file(&[sst_path], &ingest_opts).unwrap();
+        assert!(db.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "v1");
+        assert!(db.get(b"k2").unwrap().unwrap().to_utf8().unwrap() == "v2");
+        assert!(db.get(b"k3").unwrap().is_none());
+
+        db.ingest_external_file_cf(cf1, &[sst_cf_path], &ingest_opts)
+          .unwrap();
+        assert!(db.get_cf(cf1, b"k4").unwrap().unwrap().to_utf8().unwrap() ==
+                "v4");
+        assert!(db.get(b"k4").unwrap().is_none());
+    }
+    let _ = fs::remove_file(sst_path);
+    let _ = fs::remove_file(sst_cf_path);
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_ingest_overlap_without_global_seqno() {
+    let path = "_rust_rocksdb_sstfilewritertest_overlap";
+    let sst_path = "_rust_rocksdb_sstfilewritertest_overlap.sst";
+    {
+        let opts = Options::new();
+        let mut writer = SstFileWriter::create(&opts);
+        writer.open(sst_path).unwrap();
+        writer.put(b"k1", b"new").unwrap();
+        writer.finish().unwrap();
+
+        let db = DB::open_default(path).unwrap();
+        db.put(b"k1", b"old").unwrap();
+        db.flush().unwrap();
+
+        // the file overlaps existing data, so it needs a global seqno
+        let mut ingest_opts = IngestExternalFileOptions::new();
+        ingest_opts.set_allow_global_seqno(false);
+        assert!(db.ingest_external_file(&[sst_path], &ingest_opts).is_err());
+        assert!(db.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "old");
+    }
+    let _ = fs::remove_file(sst_path);
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
@@ -0,0 +1,47 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use rocksdb::{DB, Histogram, Options, Ticker, Writable};
+
+#[test]
+pub fn test_statistics() {
+    let path = "_rust_rocksdb_statisticstest";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let stats = opts.enable_statistics();
+        let db = DB::open(&opts, path).unwrap();
+        for i in 0..100 {
+            db.put(format!("k{}", i).as_bytes(), b"value").unwrap();
+        }
+        for i in 0..10 {
+            db.get(format!("k{}", i).as_bytes()).unwrap();
+        }
+
+        assert_eq!(stats.get_ticker_count(Ticker::NumberKeysWritten), 100);
+        assert_eq!(stats.get_ticker_count(Ticker::NumberKeysRead), 10);
+        assert!(stats.get_ticker_count(Ticker::BytesWritten) > 0);
+
+        let writes = stats.get_histogram_data(Histogram::DbWrite);
+        assert_eq!(writes.count, 100);
+        assert!(writes.p99 >= writes.median);
+        assert!(writes.max >= writes.min);
+
+        let dump = stats.to_string();
+        assert!(dump.contains(Ticker::NumberKeysWritten.name()));
+        assert!(dump.contains(Histogram::DbGet.name()));
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
@@ -0,0 +1,266 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use rocksdb::{ColumnFamilyDescriptor, DB, MergeOperands,
+              OptimisticTransactionDB, OptimisticTransactionOptions, Options,
+              TransactionDB, TransactionDBOptions, TransactionOptions,
+              Writable};
+
+#[test]
+pub fn test_optimistic_transaction() {
+    let path = "_rust_rocksdb_optimistictransactiontest";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let db = OptimisticTransactionDB::open(&opts, path).unwrap();
+
+        let txn = db.transaction();
+        txn.put(b"k1", b"v1").unwrap();
+        txn.put(b"k2", b"v2").unwrap();
+        txn.delete(b"k2").unwrap();
+        // a transaction sees its own writes, nobody else does until commit
+        assert!(txn.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "v1");
+        assert!(db.get(b"k1").unwrap().is_none());
+        txn.commit().unwrap();
+        assert!(db.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "v1");
+        assert!(db.get(b"k2").unwrap().is_none());
+
+        // dropping a transaction discards it
+        {
+            let txn = db.transaction();
+            txn.put(b"k3", b"v3").unwrap();
+        }
+        assert!(db.get(b"k3").unwrap().is_none());
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_optimistic_transaction_conflict() {
+    let path = "_rust_rocksdb_optimistictransactiontest_conflict";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let db = OptimisticTransactionDB::open(&opts, path).unwrap();
+
+        let txn1 = db.transaction();
+        let txn2 = db.transaction();
+        txn1.get_for_update(b"counter", true).unwrap();
+        txn2.get_for_update(b"counter", true).unwrap();
+        txn2.put(b"counter", b"2").unwrap();
+        txn2.commit().unwrap();
+
+        txn1.put(b"counter", b"1").unwrap();
+        let err = txn1.commit().unwrap_err();
+        assert!(err.starts_with("Resource busy"), "{}", err);
+        assert!(db.get(b"counter").unwrap().unwrap().to_utf8().unwrap() ==
+                "2");
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_optimistic_transaction_snapshot() {
+    let path = "_rust_rocksdb_optimistictransactiontest_snapshot";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let db = OptimisticTransactionDB::open(&opts, path).unwrap();
+        let setup = db.transaction();
+        setup.put(b"k1", b"v1").unwrap();
+        setup.commit().unwrap();
+
+        let mut txn_opts = OptimisticTransactionOptions::new();
+        txn_opts.set_snapshot(true);
+        let txn = db.transaction_opt(&txn_opts);
+
+        let other = db.transaction();
+        other.put(b"k1", b"changed").unwrap();
+        other.commit().unwrap();
+
+        // reads stay at the snapshot taken when txn began
+        assert!(txn.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "v1");
+        txn.put(b"k1", b"mine").unwrap();
+        assert!(txn.commit().unwrap_err().starts_with("Resource busy"));
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_transaction_db_locks() {
+    let path = "_rust_rocksdb_transactiondbtest_locks";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let mut txn_db_opts = TransactionDBOptions::new();
+        txn_db_opts.set_transaction_lock_timeout(10);
+        let db = TransactionDB::open(&opts, &txn_db_opts, path).unwrap();
+
+        let txn1 = db.transaction();
+        assert!(txn1.get_for_update(b"k1", true).unwrap().is_none());
+
+        // k1 is locked by txn1, so txn2 times out instead of conflicting
+        let mut txn_opts = TransactionOptions::new();
+        txn_opts.set_lock_timeout(10);
+        let txn2 = db.transaction_opt(&txn_opts);
+        let err = txn2.put(b"k1", b"v2").unwrap_err();
+        assert!(err.starts_with("Operation timed out"), "{}", err);
+
+        txn1.put(b"k1", b"v1").unwrap();
+        txn1.commit().unwrap();
+        txn2.put(b"k1", b"v2").unwrap();
+        txn2.commit().unwrap();
+        assert!(db.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "v2");
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_transaction_db_deadlock_detect() {
+    let path = "_rust_rocksdb_transactiondbtest_deadlock";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let db = TransactionDB::open(&opts, &TransactionDBOptions::new(), path)
+                     .unwrap();
+        let db = Arc::new(db);
+        let barrier = Arc::new(Barrier::new(2));
+
+        // each transaction locks one key, then wants the other's
+        let lock_both = |first: &'static [u8], second: &'static [u8]| {
+            let db = db.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let mut txn_opts = TransactionOptions::new();
+                txn_opts.set_deadlock_detect(true);
+                // long enough that only deadlock detection can end the wait
+                txn_opts.set_lock_timeout(10 * 1000);
+                let txn = db.transaction_opt(&txn_opts);
+                txn.put(first, b"v").unwrap();
+                barrier.wait();
+                // the transaction that loses is rolled back when dropped,
+                // which lets the other one through
+                txn.put(second, b"v")
+            })
+        };
+        let t1 = lock_both(b"k1", b"k2");
+        let t2 = lock_both(b"k2", b"k1");
+        let results = vec![t1.join().unwrap(), t2.join().unwrap()];
+
+        let errs: Vec<&String> = results.iter()
+                                        .filter_map(|r| r.as_ref().err())
+                                        .collect();
+        assert_eq!(errs.len(), 1, "{:?}", results);
+        assert!(errs[0].starts_with("Resource busy"), "{}", errs[0]);
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_transaction_db_savepoints() {
+    let path = "_rust_rocksdb_transactiondbtest_savepoints";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let db = TransactionDB::open(&opts, &TransactionDBOptions::new(), path)
+                     .unwrap();
+
+        let txn = db.transaction();
+        assert!(txn.rollback_to_savepoint().is_err());
+        txn.put(b"k1", b"v1").unwrap();
+        txn.set_savepoint();
+        txn.put(b"k2", b"v2").unwrap();
+        txn.put(b"k1", b"changed").unwrap();
+        txn.rollback_to_savepoint().unwrap();
+        assert!(txn.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "v1");
+        assert!(txn.get(b"k2").unwrap().is_none());
+        txn.commit().unwrap();
+        assert!(db.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "v1");
+
+        let txn = db.transaction();
+        txn.put(b"k3", b"v3").unwrap();
+        txn.rollback().unwrap();
+        assert!(db.get(b"k3").unwrap().is_none());
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_transaction_db_two_phase_commit() {
+    let path = "_rust_rocksdb_transactiondbtest_2pc";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let db = TransactionDB::open(&opts, &TransactionDBOptions::new(), path)
+                     .unwrap();
+
+        let txn = db.transaction();
+        txn.put(b"k1", b"v1").unwrap();
+        // only named transactions can be prepared
+        assert!(txn.prepare().is_err());
+        txn.set_name("txn1").unwrap();
+        txn.prepare().unwrap();
+        assert!(db.get(b"k1").unwrap().is_none());
+        txn.commit().unwrap();
+        assert!(db.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "v1");
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+fn concat_merge(_: &[u8],
+                existing_val: Option<&[u8]>,
+                operands: &mut MergeOperands)
+                -> Vec<u8> {
+    let mut result: Vec<u8> = existing_val.map(|v| v.to_vec())
+                                          .unwrap_or(vec![]);
+    for op in operands {
+        result.extend_from_slice(op);
+    }
+    result
+}
+
+#[test]
+pub fn test_transaction_db_column_family_options() {
+    let path = "_rust_rocksdb_transactiondbtest_cf_options";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        {
+            let mut db = DB::open(&opts, path).unwrap();
+            db.create_cf("merged", &Options::new()).unwrap();
+        }
+        let mut cf_opts = Options::new();
+        cf_opts.add_merge_operator("concat", concat_merge);
+        let db = TransactionDB::open_cf_descriptors(
+                     &opts,
+                     &TransactionDBOptions::new(),
+                     path,
+                     vec![ColumnFamilyDescriptor::new("merged", cf_opts)])
+                     .unwrap();
+        let cf = *db.cf_handle("merged").unwrap();
+
+        // the column family got its own options, merge operator included
+        let txn = db.transaction();
+        txn.put_cf(cf, b"k1", b"a").unwrap();
+        txn.merge_cf(cf, b"k1", b"b").unwrap();
+        txn.commit().unwrap();
+        assert!(db.get_cf(cf, b"k1").unwrap().unwrap().to_utf8().unwrap() ==
+                "ab");
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
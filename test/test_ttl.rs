@@ -0,0 +1,73 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use std::thread;
+use std::time::Duration;
+
+use rocksdb::{ColumnFamilyDescriptor, DB, Options, Writable};
+
+#[test]
+pub fn test_ttl() {
+    let path = "_rust_rocksdb_ttltest";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let db = DB::open_with_ttl(&opts, path, 1).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        assert!(db.get(b"k1").unwrap().unwrap().to_utf8().unwrap() == "v1");
+
+        thread::sleep(Duration::from_secs(2));
+        db.compact_range(None, None);
+        assert!(db.get(b"k1").unwrap().is_none());
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
+
+#[test]
+pub fn test_ttl_cf() {
+    let path = "_rust_rocksdb_ttltest_cf";
+    {
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        let mut db = DB::open_with_ttl(&opts, path, 0).unwrap();
+        db.create_cf("short", &Options::new()).unwrap();
+        db.create_cf("long", &Options::new()).unwrap();
+    }
+    {
+        let cfs = vec![(ColumnFamilyDescriptor::new("short", Options::new()),
+                        1),
+                       (ColumnFamilyDescriptor::new("long", Options::new()),
+                        3600)];
+        let db = DB::open_cf_descriptors_with_ttl(&Options::new(), path, 1, cfs)
+                     .unwrap();
+        let short = *db.cf_handle("short").unwrap();
+        let long = *db.cf_handle("long").unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put_cf(short, b"k2", b"v2").unwrap();
+        db.put_cf(long, b"k3", b"v3").unwrap();
+
+        thread::sleep(Duration::from_secs(2));
+        db.compact_range(None, None);
+        db.compact_range_cf(short, None, None);
+        db.compact_range_cf(long, None, None);
+        // "default" was left out of the descriptors, so it gets the
+        // database's TTL
+        assert!(db.get(b"k1").unwrap().is_none());
+        assert!(db.get_cf(short, b"k2").unwrap().is_none());
+        assert!(db.get_cf(long, b"k3").unwrap().unwrap().to_utf8().unwrap() ==
+                "v3");
+    }
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+}
@@ -0,0 +1,109 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use std::fs;
+
+use rocksdb::{DB, IteratorMode, Options, Writable, WriteBatch};
+
+fn wal_opts() -> Options {
+    let mut opts = Options::new();
+    opts.create_if_missing(true);
+    // keep flushed log files around so they can still be replayed
+    opts.set_wal_ttl_seconds(3600);
+    opts.set_wal_size_limit_mb(64);
+    opts
+}
+
+// Applies every batch from `since` onwards to `replica`, returning the
+// sequence number to continue from.
+fn replay(primary: &DB, replica: &DB, since: u64) -> u64 {
+    let mut next = since;
+    for update in primary.get_updates_since(since).unwrap() {
+        let (seq, batch) = update.unwrap();
+        next = seq + batch.len() as u64;
+        replica.write(batch).unwrap();
+    }
+    next
+}
+
+// DestroyDB tries to remove the database directory before the WAL archive
+// inside it, which leaves the empty directory behind.
+fn destroy(path: &str) {
+    assert!(DB::destroy(&Options::new(), path).is_ok());
+    let _ = fs::remove_dir_all(path);
+}
+
+fn contents(db: &DB) -> Vec<(Box<[u8]>, Box<[u8]>)> {
+    db.iterator(IteratorMode::Start).collect()
+}
+
+#[test]
+pub fn test_wal_options() {
+    let opts = wal_opts();
+    assert_eq!(opts.get_wal_ttl_seconds(), 3600);
+    assert_eq!(opts.get_wal_size_limit_mb(), 64);
+}
+
+#[test]
+pub fn test_replay_updates() {
+    let primary_path = "_rust_rocksdb_waltest_primary";
+    let replica_path = "_rust_rocksdb_waltest_replica";
+    {
+        let primary = DB::open(&wal_opts(), primary_path).unwrap();
+        let replica = DB::open(&wal_opts(), replica_path).unwrap();
+        assert_eq!(primary.latest_sequence_number(), 0);
+
+        primary.put(b"k1", b"v1").unwrap();
+        primary.put(b"k2", b"v2").unwrap();
+        let batch = WriteBatch::new();
+        batch.put(b"k3", b"v3").unwrap();
+        batch.delete(b"k1").unwrap();
+        assert_eq!(batch.len(), 2);
+        primary.write(batch).unwrap();
+        assert_eq!(primary.latest_sequence_number(), 4);
+        // flushed log files are archived, not lost
+        primary.flush().unwrap();
+
+        let next = replay(&primary, &replica, 1);
+        assert_eq!(next, 5);
+        assert_eq!(contents(&replica), contents(&primary));
+
+        // pick up where the last replay stopped
+        primary.put(b"k4", b"v4").unwrap();
+        primary.delete(b"k2").unwrap();
+        let mut updates = primary.get_updates_since(next).unwrap();
+        let (seq, batch) = updates.next().unwrap().unwrap();
+        assert_eq!(seq, 5);
+        assert_eq!(batch.len(), 1);
+        drop(updates);
+
+        assert_eq!(replay(&primary, &replica, next), 7);
+        assert_eq!(contents(&replica), contents(&primary));
+        assert!(replica.get(b"k2").unwrap().is_none());
+    }
+    destroy(primary_path);
+    destroy(replica_path);
+}
+
+#[test]
+pub fn test_updates_since_unwritten_sequence() {
+    let path = "_rust_rocksdb_waltest_unwritten";
+    {
+        let db = DB::open(&wal_opts(), path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        assert!(db.get_updates_since(100).is_err());
+    }
+    destroy(path);
+}
@@ -0,0 +1,60 @@
+//
+// Copyright 2014 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use rocksdb::{Cache, DB, Options, Writable, WriteBufferManager};
+
+#[test]
+pub fn test_write_buffer_manager() {
+    let wbm = WriteBufferManager::new(0, false);
+    assert!(!wbm.enabled());
+    assert!(!wbm.cost_to_cache());
+
+    wbm.set_buffer_size(64 << 20);
+    wbm.set_allow_stall(true);
+    assert!(wbm.enabled());
+    assert_eq!(wbm.buffer_size(), 64 << 20);
+}
+
+#[test]
+pub fn test_shared_write_buffer_manager() {
+    let path1 = "_rust_rocksdb_writebuffermanagertest1";
+    let path2 = "_rust_rocksdb_writebuffermanagertest2";
+    {
+        let cache = Cache::new_lru_cache(64 << 20);
+        let wbm = WriteBufferManager::new_with_cache(32 << 20, &cache, false);
+        assert!(wbm.enabled());
+        assert!(wbm.cost_to_cache());
+
+        let mut opts = Options::new();
+        opts.create_if_missing(true);
+        opts.set_write_buffer_manager(&wbm);
+        let db1 = DB::open(&opts, path1).unwrap();
+        let db2 = DB::open(&opts, path2).unwrap();
+
+        let value = "x".repeat(1 << 10);
+        for i in 0..1000 {
+            let key = format!("k{}", i);
+            db1.put(key.as_bytes(), value.as_bytes()).unwrap();
+            db2.put(key.as_bytes(), value.as_bytes()).unwrap();
+        }
+        // both databases' memtables are counted, and charged to the cache
+        assert!(wbm.memory_usage() >= 2000 << 10);
+        assert!(wbm.mutable_memtable_memory_usage() <= wbm.memory_usage());
+        assert!(wbm.dummy_entries_in_cache_usage() > 0);
+        assert!(cache.get_usage() >= wbm.dummy_entries_in_cache_usage());
+    }
+    assert!(DB::destroy(&Options::new(), path1).is_ok());
+    assert!(DB::destroy(&Options::new(), path2).is_ok());
+}